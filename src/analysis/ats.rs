@@ -0,0 +1,391 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use colored::Colorize;
+use eyre::Result;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::chat::agent::LLMResumeOutput;
+use crate::scraper::job::JobDescription;
+
+/// Known skills as (canonical name, other spellings). Matching is
+/// case-insensitive, except for names of two characters or less.
+static KEYWORDS: &[(&str, &[&str])] = &[
+    // languages
+    ("Rust", &[]),
+    ("Go", &["golang"]),
+    ("Python", &[]),
+    ("Java", &[]),
+    ("Kotlin", &[]),
+    ("Scala", &[]),
+    ("C", &[]),
+    ("C++", &["cpp"]),
+    ("C#", &["csharp", "c sharp"]),
+    ("JavaScript", &["js", "ecmascript"]),
+    ("TypeScript", &["TS"]),
+    ("Ruby", &[]),
+    ("PHP", &[]),
+    ("Swift", &[]),
+    ("Elixir", &[]),
+    ("Haskell", &[]),
+    ("Lua", &[]),
+    ("Zig", &[]),
+    ("Nix", &["nixos"]),
+    ("Bash", &["shell scripting", "shell"]),
+    ("SQL", &[]),
+    ("HTML", &["html5"]),
+    ("CSS", &["css3", "sass", "scss"]),
+    ("WebAssembly", &["wasm"]),
+    ("Solidity", &[]),
+    // frameworks and runtimes
+    ("Node.js", &["nodejs"]),
+    ("React", &["react.js", "reactjs"]),
+    ("Next.js", &["nextjs"]),
+    ("Vue", &["vue.js", "vuejs"]),
+    ("Angular", &["angularjs"]),
+    ("Svelte", &["sveltekit"]),
+    ("Django", &[]),
+    ("Flask", &[]),
+    ("FastAPI", &[]),
+    ("Spring", &["spring boot", "springboot"]),
+    (".NET", &["dotnet", "asp.net"]),
+    ("Rails", &["ruby on rails", "ror"]),
+    ("Express", &["express.js", "expressjs"]),
+    ("Tokio", &[]),
+    ("Actix", &["actix-web"]),
+    ("Axum", &[]),
+    ("Tauri", &[]),
+    ("React Native", &[]),
+    ("Flutter", &[]),
+    ("Tailwind", &["tailwindcss"]),
+    // data and messaging
+    ("PostgreSQL", &["postgres", "psql"]),
+    ("MySQL", &["mariadb"]),
+    ("SQLite", &[]),
+    ("MongoDB", &["mongo"]),
+    ("Redis", &[]),
+    ("Elasticsearch", &["elastic search", "opensearch"]),
+    ("Kafka", &["apache kafka"]),
+    ("RabbitMQ", &["amqp"]),
+    ("GraphQL", &[]),
+    ("gRPC", &["protobuf", "protocol buffers"]),
+    ("REST", &["restful", "rest api", "rest apis"]),
+    ("NoSQL", &[]),
+    ("Spark", &["apache spark", "pyspark"]),
+    ("Pandas", &[]),
+    // infrastructure
+    ("Docker", &["containers", "containerization"]),
+    ("Kubernetes", &["k8s"]),
+    ("Terraform", &["infrastructure as code", "iac"]),
+    ("Ansible", &[]),
+    ("AWS", &["amazon web services"]),
+    ("GCP", &["google cloud", "google cloud platform"]),
+    ("Azure", &["microsoft azure"]),
+    ("Linux", &["unix"]),
+    ("Git", &["github", "gitlab"]),
+    (
+        "CI/CD",
+        &[
+            "CI",
+            "CD",
+            "continuous integration",
+            "continuous delivery",
+            "github actions",
+        ],
+    ),
+    ("Microservices", &["microservice", "micro-services"]),
+    ("Serverless", &["lambda"]),
+    ("Nginx", &[]),
+    ("Prometheus", &["grafana"]),
+    ("Observability", &["monitoring", "opentelemetry"]),
+    // practices and domains
+    ("Machine Learning", &["ML", "aprendizado de máquina"]),
+    ("Deep Learning", &[]),
+    (
+        "LLM",
+        &["llms", "large language models", "generative ai", "genai"],
+    ),
+    ("PyTorch", &["torch"]),
+    ("TensorFlow", &[]),
+    ("Data Structures", &["algorithms", "estruturas de dados"]),
+    ("Distributed Systems", &["sistemas distribuídos"]),
+    (
+        "Concurrency",
+        &["concorrência", "async", "asynchronous", "multithreading"],
+    ),
+    ("Testing", &["unit tests", "unit testing", "tdd", "testes"]),
+    ("Agile", &["scrum", "kanban", "ágil"]),
+    (
+        "Security",
+        &["segurança", "cybersecurity", "oauth", "authentication"],
+    ),
+    ("Networking", &["tcp/ip", "redes", "tcp", "udp"]),
+    ("Embedded", &["embarcados", "firmware", "microcontrollers"]),
+    ("Blockchain", &["web3", "ethereum"]),
+    ("API Design", &["api", "apis"]),
+    ("Performance", &["optimization", "otimização", "profiling"]),
+];
+
+/// Acronyms and words that look technical but are not skills.
+static STOPWORDS: &[&str] = &[
+    "US", "USA", "EU", "UK", "BR", "PJ", "CLT", "LLC", "HR", "RH", "CEO", "CTO", "OK", "FAQ",
+    "PTO", "EEO", "ID", "IT", "TI", "AI", "PhD", "BSc", "MSc",
+];
+
+/// The known skills, compiled once.
+static KNOWN_KEYWORDS: LazyLock<Vec<Keyword>> = LazyLock::new(|| {
+    KEYWORDS
+        .iter()
+        .map(|(name, synonyms)| Keyword::new(name, synonyms))
+        .collect()
+});
+
+/// CamelCase words, acronyms and names with `.js`, `++` or `#`
+static TERM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:[A-Z][a-z0-9]*[A-Z][A-Za-z0-9]*|[A-Za-z][\w-]*(?:\.js|\+\+|#))")
+        .expect("valid term regex")
+});
+
+/// How many of the job's keywords the generated resume covers.
+#[derive(Debug, Serialize)]
+pub struct KeywordReport {
+    /// Percentage of job keywords found in the resume
+    pub score: f64,
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+/// A keyword with every spelling that counts as a match.
+#[derive(Clone)]
+struct Keyword {
+    name: String,
+    patterns: Vec<Regex>,
+}
+
+impl Keyword {
+    fn new(name: &str, synonyms: &[&str]) -> Self {
+        let patterns = std::iter::once(name)
+            .chain(synonyms.iter().copied())
+            .map(|form| {
+                // `+`, `#` and `.` are part of names like C++, C# and Node.js
+                let case = if form.chars().count() <= 2 {
+                    ""
+                } else {
+                    "(?i)"
+                };
+                Regex::new(&format!(
+                    r"{}(?:^|[^\w+#.]){}(?:$|[^\w+#])",
+                    case,
+                    regex::escape(form)
+                ))
+                .expect("valid keyword regex")
+            })
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            patterns,
+        }
+    }
+
+    fn is_in(&self, text: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.is_match(text))
+    }
+}
+
+/// Whether `text` mentions `term` as a whole word (ignoring case, unless
+/// the term is two characters or less).
+pub fn mentions(text: &str, term: &str) -> bool {
+    Keyword::new(term, &[]).is_in(text)
+}
+
+/// Keywords mentioned in the job's requirements and description: known
+/// skills (by any spelling) plus technical-looking terms such as `GraphQL`,
+/// `AWS` or `Node.js` that aren't in the known list.
+fn job_keywords(job: &JobDescription) -> Vec<Keyword> {
+    let text = format!("{}\n{}", job.requirements, job.description);
+
+    let mut keywords: Vec<Keyword> = KNOWN_KEYWORDS
+        .iter()
+        .filter(|keyword| keyword.is_in(&text))
+        .cloned()
+        .collect();
+
+    let mut seen = BTreeSet::new();
+    for found in TERM.find_iter(&text) {
+        let found = found.as_str();
+        // long all-caps words are usually headings ("REQUIREMENTS"), not acronyms
+        let heading = found.len() > 5 && found.chars().all(|c| !c.is_lowercase());
+        if heading
+            || STOPWORDS.contains(&found)
+            || !seen.insert(found.to_lowercase())
+            || keywords.iter().any(|keyword| keyword.is_in(found))
+        {
+            continue;
+        }
+        keywords.push(Keyword::new(found, &[]));
+    }
+
+    keywords
+}
+
+/// All the text of the generated resume, as an ATS would read it.
+fn resume_text(output: &LLMResumeOutput) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+
+    for category in &output.skills_by_category {
+        parts.push(&category.category);
+        parts.extend(category.items.iter().map(String::as_str));
+    }
+    for project in &output.projects {
+        parts.push(&project.title);
+        parts.extend(project.items.iter().map(String::as_str));
+    }
+    for experience in &output.experience {
+        parts.extend([experience.position.as_str(), experience.company.as_str()]);
+        parts.extend(experience.accomplishments.iter().map(String::as_str));
+    }
+    for education in &output.education {
+        parts.push(&education.degree);
+        parts.extend(education.accomplishments.iter().map(String::as_str));
+    }
+    for contribution in &output.contributions {
+        parts.push(&contribution.project);
+        parts.extend(contribution.items.iter().map(String::as_str));
+    }
+
+    // drop the inline **bold** / `code` markers
+    parts.join("\n").replace("**", "").replace('`', "")
+}
+
+pub fn analyze(job: &JobDescription, output: &LLMResumeOutput) -> KeywordReport {
+    let text = resume_text(output);
+    let (matched, missing): (Vec<_>, Vec<_>) = job_keywords(job)
+        .into_iter()
+        .partition(|keyword| keyword.is_in(&text));
+
+    let total = matched.len() + missing.len();
+    let score = if total == 0 {
+        100.0
+    } else {
+        (matched.len() as f64 / total as f64 * 1000.0).round() / 10.0
+    };
+
+    KeywordReport {
+        score,
+        matched: matched.into_iter().map(|keyword| keyword.name).collect(),
+        missing: missing.into_iter().map(|keyword| keyword.name).collect(),
+    }
+}
+
+impl KeywordReport {
+    pub fn print(&self) {
+        let score = format!("{:.1}%", self.score);
+        let score = match self.score {
+            s if s >= 75.0 => score.green(),
+            s if s >= 50.0 => score.yellow(),
+            _ => score.red(),
+        };
+
+        println!("\n{}", "=== ATS Keyword Coverage ===".cyan().bold());
+        println!(
+            "{} {} ({} of {} keywords)",
+            "Score:".bold(),
+            score.bold(),
+            self.matched.len(),
+            self.matched.len() + self.missing.len()
+        );
+        println!(
+            "{} {}",
+            "Matched:".green().bold(),
+            if self.matched.is_empty() {
+                "-".to_string()
+            } else {
+                self.matched.join(", ")
+            }
+        );
+        println!(
+            "{} {}\n",
+            "Missing:".red().bold(),
+            if self.missing.is_empty() {
+                "-".to_string()
+            } else {
+                self.missing.join(", ")
+            }
+        );
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::agent::SkillCategory;
+
+    fn job(requirements: &str) -> JobDescription {
+        JobDescription {
+            title: "Backend Engineer".to_string(),
+            company: None,
+            description: String::new(),
+            requirements: requirements.to_string(),
+        }
+    }
+
+    fn resume(skills: &[&str]) -> LLMResumeOutput {
+        LLMResumeOutput {
+            skills_by_category: vec![SkillCategory {
+                category: "Skills".to_string(),
+                items: skills.iter().map(|skill| skill.to_string()).collect(),
+            }],
+            projects: Vec::new(),
+            education: Vec::new(),
+            experience: Vec::new(),
+            contributions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn mentions_whole_words_only() {
+        assert!(mentions("Built with Rust and Go", "rust"));
+        assert!(!mentions("Trusted partner", "Rust"));
+        assert!(mentions("C++ and C# services", "C++"));
+        assert!(!mentions("Services in C#", "C"));
+        assert!(mentions("A Node.js API", "Node.js"));
+    }
+
+    #[test]
+    fn short_terms_match_case() {
+        assert!(mentions("Written in Go", "Go"));
+        assert!(!mentions("Ready to go live", "Go"));
+    }
+
+    #[test]
+    fn synonyms_count_as_matches() {
+        let report = analyze(
+            &job("Experience with Kubernetes and PostgreSQL"),
+            &resume(&["k8s", "Postgres"]),
+        );
+
+        assert_eq!(report.matched, ["PostgreSQL", "Kubernetes"]);
+        assert!(report.missing.is_empty());
+        assert_eq!(report.score, 100.0);
+    }
+
+    #[test]
+    fn reports_missing_keywords() {
+        let report = analyze(
+            &job("Rust, GraphQL and AWS. REQUIREMENTS listed in the USA."),
+            &resume(&["Rust"]),
+        );
+
+        assert_eq!(report.matched, ["Rust"]);
+        assert_eq!(report.missing, ["GraphQL", "AWS"]);
+        assert_eq!(report.score, 33.3);
+    }
+}
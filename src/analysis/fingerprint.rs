@@ -0,0 +1,526 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use eyre::{Result, eyre};
+use log::{debug, info, warn};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::scraper::forge::RepoData;
+use crate::scraper::local::{git, repo_name};
+use crate::utils::config::Config;
+
+/// Dependency manifests, by file name.
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "requirements.txt",
+];
+
+/// Manifests read per repository, so monorepos don't flood the prompt.
+const MAX_MANIFESTS: usize = 6;
+
+/// Dependencies listed per manifest.
+const MAX_DEPENDENCIES: usize = 15;
+
+const MAX_MODULES: usize = 20;
+
+/// Files larger than this are not searched for tests.
+const MAX_FILE_SIZE: u64 = 512 * 1024;
+
+/// A Dockerfile's `FROM` lines, with the image and the stage name.
+static FROM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^\s*FROM\s+(?:--platform=\S+\s+)?(\S+)(?:\s+AS\s+(\S+))?")
+        .expect("valid FROM pattern")
+});
+
+/// Test function declarations, by language.
+static TEST_FUNCTIONS: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        ("rust", r"#\[(?:[\w:]+::)?test\]"),
+        ("python", r"(?m)^\s*(?:async\s+)?def test_\w*"),
+        ("go", r"(?m)^func Test\w*\("),
+        ("javascript", r"(?m)^\s*(?:it|test)(?:\.\w+)?\("),
+        ("java", r"@Test\b"),
+        ("ruby", r"(?m)^\s*it\s"),
+        ("elixir", r"(?m)^\s*test\s+\x22"),
+    ]
+    .into_iter()
+    .map(|(language, pattern)| (language, Regex::new(pattern).expect("valid test pattern")))
+    .collect()
+});
+
+/// Directories holding third-party code.
+const VENDORED: &[&str] = &["node_modules/", "vendor/", "third_party/"];
+
+/// CI systems, by path prefix of their configuration.
+const CI_SYSTEMS: &[(&str, &str)] = &[
+    (".github/workflows/", "GitHub Actions"),
+    (".gitlab-ci.yml", "GitLab CI"),
+    (".forgejo/workflows/", "Forgejo Actions"),
+    (".gitea/workflows/", "Gitea Actions"),
+    (".circleci/", "CircleCI"),
+    (".travis.yml", "Travis CI"),
+    ("Jenkinsfile", "Jenkins"),
+    ("azure-pipelines.yml", "Azure Pipelines"),
+    ("bitbucket-pipelines.yml", "Bitbucket Pipelines"),
+    (".woodpecker", "Woodpecker CI"),
+    (".drone.yml", "Drone CI"),
+];
+
+/// What a repository is built with, read from its files instead of guessed
+/// from the README.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TechFingerprint {
+    /// `RepoData::url` of the analyzed repository
+    pub repository: String,
+    pub manifests: Vec<Manifest>,
+    /// CI systems, e.g. `GitHub Actions`
+    pub ci: Vec<String>,
+    /// Docker base images, Compose and Helm
+    pub containers: Vec<String>,
+    /// Test functions found in the source
+    pub tests: u64,
+    pub test_files: u64,
+    /// Top-level directories and the entries of `src/`
+    pub modules: Vec<String>,
+}
+
+/// Direct dependencies declared in one manifest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub path: String,
+    pub dependencies: Vec<String>,
+}
+
+/// e.g. `Cargo.toml: tokio, serde; CI: GitHub Actions; tests: 42 in 6 files`
+impl fmt::Display for TechFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = self
+            .manifests
+            .iter()
+            .map(|manifest| {
+                let mut dependencies = manifest
+                    .dependencies
+                    .iter()
+                    .take(MAX_DEPENDENCIES)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if manifest.dependencies.len() > MAX_DEPENDENCIES {
+                    dependencies.push_str(&format!(
+                        " (+{} more)",
+                        manifest.dependencies.len() - MAX_DEPENDENCIES
+                    ));
+                }
+                format!("{}: {}", manifest.path, dependencies)
+            })
+            .collect();
+
+        if !self.ci.is_empty() {
+            parts.push(format!("CI: {}", self.ci.join(", ")));
+        }
+        if !self.containers.is_empty() {
+            parts.push(format!("containers: {}", self.containers.join(", ")));
+        }
+        if self.tests > 0 {
+            parts.push(format!(
+                "tests: {} in {} files",
+                self.tests, self.test_files
+            ));
+        }
+        if !self.modules.is_empty() {
+            parts.push(format!("modules: {}", self.modules.join(", ")));
+        }
+
+        f.write_str(&parts.join("; "))
+    }
+}
+
+impl TechFingerprint {
+    /// Reads the fingerprint from the tracked files of a working tree.
+    pub async fn from_checkout(repository: &str, dir: &Path) -> Result<Self> {
+        let listing = git(dir, &["ls-files", "-z"]).await?;
+        let files: Vec<&str> = listing
+            .split('\0')
+            .filter(|file| !file.is_empty() && !VENDORED.iter().any(|v| file.contains(v)))
+            .collect();
+
+        let mut fingerprint = TechFingerprint {
+            repository: repository.to_string(),
+            ..Default::default()
+        };
+
+        let mut manifests: Vec<&str> = files
+            .iter()
+            .copied()
+            .filter(|file| MANIFESTS.contains(&file_name(file)) && file.matches('/').count() < 3)
+            .collect();
+        // the root manifests first
+        manifests.sort_by_key(|file| file.matches('/').count());
+        for path in manifests.into_iter().take(MAX_MANIFESTS) {
+            let Ok(content) = tokio::fs::read_to_string(dir.join(path)).await else {
+                continue;
+            };
+            let dependencies = dependencies(file_name(path), &content);
+            if !dependencies.is_empty() {
+                fingerprint.manifests.push(Manifest {
+                    path: path.to_string(),
+                    dependencies,
+                });
+            }
+        }
+
+        let mut ci = BTreeSet::new();
+        let mut images = BTreeSet::new();
+        let mut containers = BTreeSet::new();
+        let mut modules = BTreeSet::new();
+        for file in &files {
+            if let Some((_, system)) = CI_SYSTEMS
+                .iter()
+                .find(|(prefix, _)| file.starts_with(prefix))
+            {
+                ci.insert(*system);
+            }
+
+            let name = file_name(file);
+            if name.starts_with("Dockerfile") || name.ends_with(".dockerfile") {
+                if let Ok(content) = tokio::fs::read_to_string(dir.join(file)).await {
+                    images.extend(base_images(&content));
+                }
+            } else if name.starts_with("docker-compose.") || name.starts_with("compose.") {
+                containers.insert("Docker Compose".to_string());
+            } else if name == "Chart.yaml" {
+                containers.insert("Helm".to_string());
+            }
+
+            if let Some((top, rest)) = file.split_once('/')
+                && !top.starts_with('.')
+            {
+                modules.insert(top.to_string());
+                if top == "src"
+                    && let Some(module) = src_module(rest)
+                {
+                    modules.insert(format!("src/{}", module));
+                }
+            }
+
+            if let Some(count) = count_tests(dir, file).await {
+                fingerprint.tests += count;
+                fingerprint.test_files += 1;
+            }
+        }
+
+        if !images.is_empty() {
+            let images: Vec<String> = images.into_iter().collect();
+            containers.insert(format!("Docker ({})", images.join(", ")));
+        }
+        fingerprint.ci = ci.into_iter().map(str::to_string).collect();
+        fingerprint.containers = containers.into_iter().collect();
+        fingerprint.modules = modules.into_iter().take(MAX_MODULES).collect();
+
+        Ok(fingerprint)
+    }
+}
+
+/// Fingerprints the selected repositories when deep analysis is enabled.
+/// Local repositories are read in place; the others are shallow-cloned into
+/// a temporary directory. Repositories that can't be read are skipped.
+pub async fn analyze_repositories(
+    config: &Config,
+    repos: &[RepoData],
+) -> Result<Vec<TechFingerprint>> {
+    if !config.analysis.deep {
+        return Ok(Vec::new());
+    }
+
+    info!("analyzing the code of {} repositories", repos.len());
+
+    let mut fingerprints = Vec::with_capacity(repos.len());
+    for repo in repos {
+        match analyze_repository(config, repo).await {
+            Ok(fingerprint) => {
+                debug!("fingerprint of {}: {}", repo.name, fingerprint);
+                fingerprints.push(fingerprint);
+            }
+            Err(e) => warn!("skipping code analysis of {}: {}", repo.name, e),
+        }
+    }
+
+    Ok(fingerprints)
+}
+
+async fn analyze_repository(config: &Config, repo: &RepoData) -> Result<TechFingerprint> {
+    if let Some(local) = config.local_repos.iter().find(|local| match &local.url {
+        Some(url) => *url == repo.url,
+        None => repo.unpublished && repo_name(local) == repo.name,
+    }) {
+        return TechFingerprint::from_checkout(&repo.url, &local.path).await;
+    }
+
+    let dir = clone_dir();
+    let result = match shallow_clone(config, repo, &dir).await {
+        Ok(()) => TechFingerprint::from_checkout(&repo.url, &dir).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+        debug!("failed to remove {}: {}", dir.display(), e);
+    }
+    result
+}
+
+/// A fresh directory for each clone, batch jobs analyzing concurrently.
+fn clone_dir() -> PathBuf {
+    static CLONES: AtomicUsize = AtomicUsize::new(0);
+    std::env::temp_dir().join(format!(
+        "auto-resume-{}-{}",
+        std::process::id(),
+        CLONES.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Clones the default branch's last commit. The token travels in an
+/// environment-provided `http.extraHeader`, out of the command line and the
+/// clone's config.
+async fn shallow_clone(config: &Config, repo: &RepoData, dir: &Path) -> Result<()> {
+    let (url, credentials) = clone_source(config, repo);
+    debug!("cloning {} into {}", url, dir.display());
+
+    let mut command = Command::new("git");
+    command
+        .args(["clone", "--quiet", "--depth", "1", "--single-branch", &url])
+        .arg(dir)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if let Some((username, token)) = credentials {
+        let basic = BASE64_STANDARD.encode(format!("{}:{}", username, token));
+        command
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env(
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {}", basic),
+            );
+    }
+
+    let output = command
+        .output()
+        .await
+        .map_err(|e| eyre!("failed to run git (is it installed?): {}", e))?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "git clone {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// The clone URL of a scraped repository, with the username and token of
+/// the host it came from.
+fn clone_source(config: &Config, repo: &RepoData) -> (String, Option<(String, String)>) {
+    let github = &config.github;
+    if let Some(path) = repo
+        .url
+        .strip_prefix(&format!("{}/repos/", github.api_url()))
+    {
+        let web = match github.api_url.as_deref() {
+            Some(api_url) => api_url.trim_end_matches('/').trim_end_matches("/api/v3"),
+            None => "https://github.com",
+        };
+        let credentials = github
+            .token
+            .clone()
+            .map(|token| (github.username.clone(), token));
+        return (format!("{}/{}.git", web, path), credentials);
+    }
+
+    let credentials = config
+        .forges
+        .iter()
+        .find(|forge| repo.url.starts_with(forge.url()))
+        .and_then(|forge| {
+            forge
+                .token
+                .clone()
+                .map(|token| (forge.username.clone(), token))
+        });
+    (
+        format!("{}.git", repo.url.trim_end_matches('/')),
+        credentials,
+    )
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// A module under `src/`: a directory, or a source file other than the
+/// crate or package entry points.
+fn src_module(path: &str) -> Option<&str> {
+    if let Some((dir, _)) = path.split_once('/') {
+        return Some(dir);
+    }
+
+    let stem = path.split('.').next().unwrap_or(path);
+    (!stem.is_empty() && !["main", "lib", "mod", "index", "__init__"].contains(&stem))
+        .then_some(stem)
+}
+
+/// Direct dependency names declared in a manifest.
+fn dependencies(manifest: &str, content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let table_keys = |value: Option<&toml::Value>| -> Vec<String> {
+        value
+            .and_then(toml::Value::as_table)
+            .map(|table| table.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+
+    match manifest {
+        "Cargo.toml" => {
+            if let Ok(cargo) = content.parse::<toml::Table>() {
+                names.extend(table_keys(cargo.get("dependencies")));
+                names.extend(table_keys(
+                    cargo.get("workspace").and_then(|w| w.get("dependencies")),
+                ));
+            }
+        }
+        "package.json" => {
+            if let Ok(package) = serde_json::from_str::<serde_json::Value>(content) {
+                for section in ["dependencies", "devDependencies"] {
+                    if let Some(dependencies) = package[section].as_object() {
+                        names.extend(dependencies.keys().cloned());
+                    }
+                }
+            }
+        }
+        "go.mod" => {
+            let mut in_require = false;
+            for line in content.lines().map(str::trim) {
+                let requirement = match line {
+                    "require (" => {
+                        in_require = true;
+                        continue;
+                    }
+                    ")" => {
+                        in_require = false;
+                        continue;
+                    }
+                    _ if in_require => line,
+                    _ => match line.strip_prefix("require ") {
+                        Some(requirement) => requirement,
+                        None => continue,
+                    },
+                };
+                if !requirement.ends_with("// indirect")
+                    && let Some(module) = requirement.split_whitespace().next()
+                {
+                    names.push(module.to_string());
+                }
+            }
+        }
+        "pyproject.toml" => {
+            if let Ok(pyproject) = content.parse::<toml::Table>() {
+                if let Some(dependencies) = pyproject
+                    .get("project")
+                    .and_then(|project| project.get("dependencies"))
+                    .and_then(toml::Value::as_array)
+                {
+                    names.extend(
+                        dependencies
+                            .iter()
+                            .filter_map(toml::Value::as_str)
+                            .filter_map(requirement_name),
+                    );
+                }
+                names.extend(
+                    table_keys(
+                        pyproject
+                            .get("tool")
+                            .and_then(|tool| tool.get("poetry"))
+                            .and_then(|poetry| poetry.get("dependencies")),
+                    )
+                    .into_iter()
+                    .filter(|name| name != "python"),
+                );
+            }
+        }
+        "requirements.txt" => {
+            names.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.starts_with('#') && !line.starts_with('-'))
+                    .filter_map(requirement_name),
+            );
+        }
+        _ => {}
+    }
+
+    let mut seen = BTreeSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    names
+}
+
+/// `requests` from a requirement like `requests[socks]>=2.31; python_version > "3.8"`
+fn requirement_name(requirement: &str) -> Option<String> {
+    let name: String = requirement
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Images of a Dockerfile's `FROM` lines, leaving out earlier build stages.
+fn base_images(dockerfile: &str) -> Vec<String> {
+    let mut stages: Vec<String> = Vec::new();
+    let mut images = Vec::new();
+    for captures in FROM.captures_iter(dockerfile) {
+        let image = captures[1].to_string();
+        if image != "scratch"
+            && !stages
+                .iter()
+                .any(|stage| stage.eq_ignore_ascii_case(&image))
+        {
+            images.push(image);
+        }
+        stages.extend(captures.get(2).map(|stage| stage.as_str().to_string()));
+    }
+    images
+}
+
+/// Test functions in a source file, `None` when it has none (or isn't
+/// source code in a language whose tests are recognized).
+async fn count_tests(dir: &Path, file: &str) -> Option<u64> {
+    let language = match file.rsplit_once('.')?.1 {
+        "rs" => "rust",
+        "py" => "python",
+        "go" if file.ends_with("_test.go") => "go",
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => "javascript",
+        "java" | "kt" => "java",
+        "rb" if file.ends_with("_spec.rb") => "ruby",
+        "ex" | "exs" => "elixir",
+        _ => return None,
+    };
+    let (_, pattern) = TEST_FUNCTIONS.iter().find(|(name, _)| *name == language)?;
+
+    let path = dir.join(file);
+    if tokio::fs::metadata(&path).await.ok()?.len() > MAX_FILE_SIZE {
+        return None;
+    }
+    let content = tokio::fs::read_to_string(&path).await.ok()?;
+    let count = pattern.find_iter(&content).count() as u64;
+
+    (count > 0).then_some(count)
+}
@@ -0,0 +1,414 @@
+use clap::ValueEnum;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::ats::mentions;
+use crate::analysis::fingerprint::TechFingerprint;
+use crate::chat::agent::{LLMResumeOutput, ProjectEntry};
+use crate::scraper::forge::RepoData;
+use crate::scraper::github::ExternalContribution;
+use crate::utils::config::ResumeConfig;
+
+/// Programming languages checked in project titles, with the GitHub
+/// linguist name they are reported under when it differs.
+static LANGUAGES: &[(&str, &str)] = &[
+    ("Rust", "Rust"),
+    ("Go", "Go"),
+    ("Golang", "Go"),
+    ("Python", "Python"),
+    ("Java", "Java"),
+    ("Kotlin", "Kotlin"),
+    ("Scala", "Scala"),
+    ("C", "C"),
+    ("C++", "C++"),
+    ("C#", "C#"),
+    ("F#", "F#"),
+    ("JavaScript", "JavaScript"),
+    ("JS", "JavaScript"),
+    ("TypeScript", "TypeScript"),
+    ("TS", "TypeScript"),
+    ("Ruby", "Ruby"),
+    ("PHP", "PHP"),
+    ("Swift", "Swift"),
+    ("Objective-C", "Objective-C"),
+    ("Dart", "Dart"),
+    ("Elixir", "Elixir"),
+    ("Erlang", "Erlang"),
+    ("Haskell", "Haskell"),
+    ("OCaml", "OCaml"),
+    ("Clojure", "Clojure"),
+    ("Lua", "Lua"),
+    ("Zig", "Zig"),
+    ("Nim", "Nim"),
+    ("Julia", "Julia"),
+    ("R", "R"),
+    ("Perl", "Perl"),
+    ("Nix", "Nix"),
+    ("Shell", "Shell"),
+    ("Bash", "Shell"),
+    ("PowerShell", "PowerShell"),
+    ("HTML", "HTML"),
+    ("CSS", "CSS"),
+    ("SCSS", "SCSS"),
+    ("Vue", "Vue"),
+    ("Svelte", "Svelte"),
+    ("Solidity", "Solidity"),
+    ("Assembly", "Assembly"),
+    ("MATLAB", "MATLAB"),
+    ("TeX", "TeX"),
+    ("LaTeX", "TeX"),
+];
+
+/// What to do with generated claims that can't be traced to source data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardMode {
+    /// Skip verification
+    Off,
+    /// Log unverified claims but keep them
+    #[default]
+    Flag,
+    /// Remove unverified claims from the resume
+    Strip,
+}
+
+/// Checks LLM output against the data it was generated from: selected
+/// repositories (links, languages, READMEs), merged pull requests and the
+/// user's own context.
+pub struct HallucinationGuard<'a> {
+    mode: GuardMode,
+    resume: &'a ResumeConfig,
+    repos: &'a [RepoData],
+    contributions: &'a [ExternalContribution],
+    fingerprints: &'a [TechFingerprint],
+    violations: usize,
+}
+
+impl<'a> HallucinationGuard<'a> {
+    pub fn new(mode: GuardMode, resume: &'a ResumeConfig, repos: &'a [RepoData]) -> Self {
+        Self {
+            mode,
+            resume,
+            repos,
+            contributions: &[],
+            fingerprints: &[],
+            violations: 0,
+        }
+    }
+
+    /// Merged pull requests that contribution entries must link to.
+    pub fn with_contributions(mut self, contributions: &'a [ExternalContribution]) -> Self {
+        self.contributions = contributions;
+        self
+    }
+
+    /// Code analyses whose dependencies count as evidence for skills.
+    pub fn with_fingerprints(mut self, fingerprints: &'a [TechFingerprint]) -> Self {
+        self.fingerprints = fingerprints;
+        self
+    }
+
+    pub fn verify(mut self, mut output: LLMResumeOutput) -> LLMResumeOutput {
+        if self.mode == GuardMode::Off {
+            return output;
+        }
+
+        output.projects = std::mem::take(&mut output.projects)
+            .into_iter()
+            .filter_map(|project| self.verify_project(project))
+            .collect();
+
+        let skill_sources = self.skill_sources();
+        for category in &mut output.skills_by_category {
+            let name = category.category.clone();
+            category.items.retain(|skill| {
+                let term = skill_term(skill);
+                // markup alone names nothing to trace
+                if !term.is_empty() && mentions(&skill_sources, &term) {
+                    debug!("guard: skill {} verified", term);
+                    return true;
+                }
+                self.decide(format!(
+                    "skill \"{}\" ({}) is not in any repository language, README or skills context",
+                    if term.is_empty() { skill.trim() } else { &term },
+                    name
+                ))
+            });
+        }
+        output
+            .skills_by_category
+            .retain(|category| !category.items.is_empty());
+
+        let experience_sources = self.experience_sources();
+        output.experience.retain(|experience| {
+            if mentions(&experience_sources, &experience.company) {
+                debug!("guard: experience at {} verified", experience.company);
+                return true;
+            }
+            self.decide(format!(
+                "experience \"{}\" at \"{}\" is not in the experience context",
+                experience.position, experience.company
+            ))
+        });
+
+        let contributions = self.contributions;
+        output.contributions.retain(|contribution| {
+            let link = repo_key(&contribution.link);
+            if contributions
+                .iter()
+                .any(|pr| repo_key(&pr.repository_url) == link)
+            {
+                debug!("guard: contribution to {} verified", contribution.project);
+                return true;
+            }
+            self.decide(format!(
+                "contribution to \"{}\" links to {}, which has no merged pull request",
+                contribution.project, contribution.link
+            ))
+        });
+
+        match (self.violations, self.mode) {
+            (0, _) => info!("guard: all generated claims verified against source data"),
+            (n, GuardMode::Strip) => info!("guard: stripped {} unverified claim(s)", n),
+            (n, _) => warn!(
+                "guard: flagged {} unverified claim(s), use `--guard strip` to remove them",
+                n
+            ),
+        }
+
+        output
+    }
+
+    fn verify_project(&mut self, mut project: ProjectEntry) -> Option<ProjectEntry> {
+        let link = repo_key(&project.link);
+        let repos = self.repos;
+        let Some(repo) = repos.iter().find(|repo| repo_key(&repo.url) == link) else {
+            let keep = self.decide(format!(
+                "project \"{}\" links to {}, which is not a selected repository",
+                project.title, project.link
+            ));
+            return keep.then_some(project);
+        };
+
+        // "Name (Rust / TypeScript)"
+        let Some((name, technologies)) = project.title.split_once('(') else {
+            debug!("guard: project {} verified", project.title);
+            return Some(project);
+        };
+
+        let repo_languages: Vec<&str> = repo
+            .languages
+            .iter()
+            .flat_map(|langs| langs.languages.iter().map(|(lang, _)| lang.as_str()))
+            .collect();
+
+        let technologies: Vec<&str> = technologies
+            .trim_end_matches(')')
+            .split(['/', ','])
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let mut kept = Vec::new();
+        for &technology in &technologies {
+            let language = LANGUAGES
+                .iter()
+                .find(|(alias, _)| alias.eq_ignore_ascii_case(technology))
+                .map(|(_, language)| *language);
+
+            match language {
+                Some(language)
+                    if !repo_languages
+                        .iter()
+                        .any(|l| l.eq_ignore_ascii_case(language)) =>
+                {
+                    if self.decide(format!(
+                        "project \"{}\" claims {}, which is not among {}'s languages ({})",
+                        project.title,
+                        technology,
+                        repo.name,
+                        repo_languages.join(", ")
+                    )) {
+                        kept.push(technology);
+                    }
+                }
+                _ => kept.push(technology),
+            }
+        }
+
+        if kept.len() < technologies.len() {
+            let name = name.trim();
+            project.title = if kept.is_empty() {
+                name.to_string()
+            } else {
+                format!("{} ({})", name, kept.join(" / "))
+            };
+        }
+
+        debug!("guard: project {} checked", project.title);
+        Some(project)
+    }
+
+    /// Logs a violation and returns whether the claim is kept.
+    fn decide(&mut self, violation: String) -> bool {
+        self.violations += 1;
+
+        match self.mode {
+            GuardMode::Strip => {
+                warn!("guard: {} (stripped)", violation);
+                false
+            }
+            _ => {
+                warn!("guard: {} (flagged)", violation);
+                true
+            }
+        }
+    }
+
+    fn skill_sources(&self) -> String {
+        let mut sources: Vec<&str> = Vec::new();
+
+        for repo in self.repos {
+            sources.extend(
+                repo.languages
+                    .iter()
+                    .flat_map(|langs| langs.languages.iter().map(|(lang, _)| lang.as_str())),
+            );
+            sources.extend(repo.readme.as_deref());
+        }
+
+        for fingerprint in self.fingerprints {
+            sources.extend(
+                fingerprint
+                    .manifests
+                    .iter()
+                    .flat_map(|manifest| manifest.dependencies.iter().map(String::as_str)),
+            );
+            sources.extend(fingerprint.ci.iter().map(String::as_str));
+            sources.extend(fingerprint.containers.iter().map(String::as_str));
+        }
+
+        sources.extend(self.resume.skills_context.as_deref());
+        sources.extend(self.resume.experience_context.as_deref());
+        sources.extend(
+            self.resume
+                .skills
+                .iter()
+                .flat_map(|item| item.items.iter().map(String::as_str)),
+        );
+
+        sources.join("\n").replace("**", "")
+    }
+
+    fn experience_sources(&self) -> String {
+        let mut sources: Vec<&str> = Vec::new();
+
+        sources.extend(self.resume.experience_context.as_deref());
+        sources.extend(
+            self.resume
+                .experience
+                .iter()
+                .filter_map(|item| item.title.as_deref()),
+        );
+
+        sources.join("\n")
+    }
+}
+
+/// The name a skill is traced by: the leading one of "Rust (Tokio, Axum)",
+/// or the first in parentheses for "(Tokio)", without Markdown markers.
+fn skill_term(skill: &str) -> String {
+    let text = skill.replace("**", "").replace('`', "");
+    let (leading, inner) = text.split_once('(').unwrap_or((&text, ""));
+    match leading.trim() {
+        "" => inner
+            .split([',', ')'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        leading => leading.to_string(),
+    }
+}
+
+/// `owner/name` for GitHub web and API URLs, `host/owner/name` for GitHub
+/// Enterprise Server ones, the bare URL otherwise (GitLab and Gitea
+/// repositories are listed by their web page).
+pub fn repo_key(url: &str) -> String {
+    let url = url
+        .trim()
+        .to_lowercase()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_string();
+
+    match url
+        .strip_prefix("api.github.com/repos/")
+        .or_else(|| url.strip_prefix("github.com/"))
+    {
+        Some(path) => path.split('/').take(2).collect::<Vec<_>>().join("/"),
+        None => match url.split_once("/api/v3/repos/") {
+            Some((host, path)) => format!(
+                "{}/{}",
+                host,
+                path.split('/').take(2).collect::<Vec<_>>().join("/")
+            ),
+            None => url,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_term_uses_the_leading_name() {
+        assert_eq!(skill_term("**Rust** (Tokio, Axum)"), "Rust");
+        assert_eq!(skill_term("`PostgreSQL`"), "PostgreSQL");
+    }
+
+    #[test]
+    fn skill_term_falls_back_to_parentheses() {
+        assert_eq!(skill_term("(Tokio)"), "Tokio");
+        assert_eq!(skill_term("**(Tokio, Axum)**"), "Tokio");
+        assert_eq!(skill_term("**`()`**"), "");
+    }
+
+    #[test]
+    fn repo_key_matches_web_and_api_urls() {
+        assert_eq!(repo_key("https://github.com/Owner/Repo"), "owner/repo");
+        assert_eq!(
+            repo_key("https://www.github.com/owner/repo.git"),
+            "owner/repo"
+        );
+        assert_eq!(
+            repo_key("https://api.github.com/repos/owner/repo/pulls/12"),
+            "owner/repo"
+        );
+        assert_eq!(repo_key("github.com/owner/repo/"), "owner/repo");
+    }
+
+    #[test]
+    fn repo_key_normalizes_enterprise_api_urls() {
+        assert_eq!(
+            repo_key("https://ghe.example.com/api/v3/repos/Owner/Repo/pulls/3"),
+            "ghe.example.com/owner/repo"
+        );
+        assert_eq!(
+            repo_key("https://ghe.example.com/api/v3/repos/owner/repo"),
+            repo_key("https://ghe.example.com/owner/repo")
+        );
+    }
+
+    #[test]
+    fn repo_key_keeps_other_hosts() {
+        assert_eq!(
+            repo_key("https://gitlab.com/group/project"),
+            "gitlab.com/group/project"
+        );
+    }
+}
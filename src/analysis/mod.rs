@@ -0,0 +1,3 @@
+pub mod ats;
+pub mod fingerprint;
+pub mod guard;
@@ -4,12 +4,19 @@ use log::{debug, info};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use crate::analysis::fingerprint::TechFingerprint;
+use crate::analysis::guard::{GuardMode, HallucinationGuard, repo_key};
+use crate::chat::provider::{LLMProvider, LLMRequest, provider_from_config};
+use crate::chat::readme::readme_snippets;
+use crate::chat::section::OutputTarget;
+use crate::chat::usage::{TokenUsage, UsageTracker, estimate_tokens};
 use crate::latex::assembler::ResumeLanguage;
-use crate::scraper::github::GitHubRepoData;
+use crate::scraper::forge::{RepoData, host_label};
+use crate::scraper::github::{ExternalContribution, PrivateLinks};
 use crate::scraper::job::JobDescription;
-use crate::utils::config::{ResumeConfig, ResumeItem};
+use crate::utils::config::{LLMConfig, ReadmeConfig, ResumeConfig, ResumeItem};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RankedRepository {
     pub rank: usize,
     pub name: String,
@@ -25,6 +32,9 @@ pub struct LLMResumeOutput {
     pub projects: Vec<ProjectEntry>,
     pub education: Vec<EducationEntry>,
     pub experience: Vec<ExperienceEntry>,
+    /// Missing from content generated before contributions were scraped
+    #[serde(default)]
+    pub contributions: Vec<ContributionEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,25 +66,88 @@ pub struct ProjectEntry {
     pub title: String,
     pub link: String,
     pub items: Vec<String>,
+    /// Links to a private repository: kept for verification, left off the resume
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hide_link: bool,
+    /// Host of the repository (GitHub, GitLab...), shown next to the title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<String>,
+}
+
+/// Merged pull requests to one upstream project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributionEntry {
+    /// `owner/name` of the upstream repository
+    pub project: String,
+    pub link: String,
+    pub items: Vec<String>,
+}
+
+/// A cover letter, split so templates can lay out each part.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverLetter {
+    /// Salutation line, e.g. "Dear Hiring Manager,"
+    pub greeting: String,
+    pub paragraphs: Vec<String>,
+    /// Sign-off phrase without the name, e.g. "Sincerely,"
+    pub closing: String,
+}
+
+/// What resume content is generated from, kept around to regenerate parts of it.
+#[derive(Clone, Copy)]
+pub struct GenerationInput<'a> {
+    pub resume_config: &'a ResumeConfig,
+    pub job_description: &'a JobDescription,
+    pub github_repos: &'a [RepoData],
+    pub contributions: &'a [ExternalContribution],
+    /// Code analyses of the repositories, when deep analysis ran
+    pub fingerprints: &'a [TechFingerprint],
+    pub language: &'a ResumeLanguage,
 }
 
 pub struct ResumeAgent {
-    api_key: String,
-    model: String,
-    endpoint: String,
+    provider: Box<dyn LLMProvider>,
+    client: reqwest::Client,
     max_retries: u32,
+    guard: GuardMode,
+    private_links: PrivateLinks,
+    readme: ReadmeConfig,
+    usage: UsageTracker,
 }
 
 impl ResumeAgent {
-    pub fn new(api_key: String, model: String, endpoint: String, max_retries: u32) -> Self {
+    pub fn new(provider: Box<dyn LLMProvider>, max_retries: u32) -> Self {
         Self {
-            api_key,
-            model,
-            endpoint,
+            usage: UsageTracker::new(provider.model(), None, None),
+            provider,
+            client: reqwest::Client::new(),
             max_retries,
+            guard: GuardMode::default(),
+            private_links: PrivateLinks::default(),
+            readme: ReadmeConfig::default(),
         }
     }
 
+    pub fn from_config(config: &LLMConfig) -> Result<Self> {
+        Ok(Self {
+            guard: config.guard,
+            readme: config.readme.clone(),
+            usage: UsageTracker::from_config(config)?,
+            ..Self::new(provider_from_config(config)?, config.max_retries)
+        })
+    }
+
+    /// Sets whether links to private repositories make it onto the resume.
+    pub fn with_private_links(mut self, private_links: PrivateLinks) -> Self {
+        self.private_links = private_links;
+        self
+    }
+
+    /// Tokens and estimated cost of the calls made so far.
+    pub fn usage(&self) -> &UsageTracker {
+        &self.usage
+    }
+
     pub async fn clean_job_description(&self, raw_html_or_text: &str) -> Result<JobDescription> {
         info!(
             "cleaning job description using LLM (max retries: {})",
@@ -96,71 +169,40 @@ impl ResumeAgent {
             raw_html_or_text
         );
 
-        let client = reqwest::Client::new();
-        let api_key = self.api_key.clone();
-        let endpoint = self.endpoint.clone();
-        let model = self.model.clone();
-
-        let result = (|| async {
-            let request_body = json!({
-                "contents": [{"parts": [{"text": prompt}]}],
-                "generationConfig": {
-                    "responseMimeType": "application/json",
-                    "responseJsonSchema": {
-                        "type": "object",
-                        "properties": {
-                            "title": {
-                                "type": "string",
-                                "description": "Job title/position name"
-                            },
-                            "company": {
-                                "type": "string",
-                                "description": "Company name (can be null if not found)"
-                            },
-                            "description": {
-                                "type": "string",
-                                "description": "Clean job description with key responsibilities"
-                            },
-                            "requirements": {
-                                "type": "string",
-                                "description": "Key technical requirements and qualifications"
-                            }
-                        },
-                        "required": ["title", "description", "requirements"]
-                    }
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "title": {
+                    "type": "string",
+                    "description": "Job title/position name"
+                },
+                "company": {
+                    "type": "string",
+                    "description": "Company name (can be null if not found)"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Clean job description with key responsibilities"
+                },
+                "requirements": {
+                    "type": "string",
+                    "description": "Key technical requirements and qualifications"
                 }
-            });
-
-            let url = format!(
-                "{}/{}:generateContent?key={}",
-                endpoint.trim_end_matches('/'),
-                model,
-                api_key
-            );
-
-            let response = client.post(&url).json(&request_body).send().await?;
-
-            if !response.status().is_success() {
-                let error = response.text().await?;
-                return Err(eyre!("LLM job cleaning failed: {}", error));
-            }
-
-            Ok(response)
-        })
-        .retry(ExponentialBuilder::default())
-        .await?;
-
-        let body: serde_json::Value = result.json().await?;
-
-        let content = body
-            .get("candidates")
-            .and_then(|c| c.get(0))
-            .and_then(|c| c.get("content"))
-            .and_then(|c| c.get("parts"))
-            .and_then(|p| p.get(0))
-            .and_then(|p| p.get("text"))
-            .and_then(|t| t.as_str())
-            .ok_or_else(|| eyre!("invalid LLM response for job cleaning"))?;
+            },
+            "required": ["title", "description", "requirements"]
+        });
+
+        let content = self
+            .complete(
+                "job cleaning",
+                &LLMRequest {
+                    system: None,
+                    prompt: &prompt,
+                    schema_name: "job_description",
+                    schema: &schema,
+                },
+            )
+            .await?;
 
         let trimmed = content.trim();
         let json_start = trimmed
@@ -183,7 +225,7 @@ impl ResumeAgent {
 
     pub async fn rank_repositories(
         &self,
-        github_repos: &[GitHubRepoData],
+        github_repos: &[RepoData],
         job_description: &JobDescription,
     ) -> Result<Vec<RankedRepository>> {
         debug!("evaluating {} repositories", github_repos.len());
@@ -205,9 +247,11 @@ impl ResumeAgent {
                 ).unwrap_or_else(|| "Unknown".to_string());
 
                 let readme_indicator = if repo.readme.is_some() { " [HAS_README]" } else { "" };
+                let authorship = repo.authorship.as_ref().map(|a| format!(" [AUTHORED: {}]", a)).unwrap_or_default();
+                let topics = if repo.topics.is_empty() { String::new() } else { format!(" [TOPICS: {}]", repo.topics.join(", ")) };
                 format!(
-                    "- {} [{}] (created: {}, last updated: {}, stars: {}, forks: {}, size: {}, commits: {}, importance: {}){}  {}",
-                    repo.name, lang_str, repo.created_at, repo.pushed_at, repo.stargazers_count, repo.forks_count, repo.size, repo.commits, repo.importance_score, readme_indicator, repo.url
+                    "- {} [{}] (created: {}, last updated: {}, stars: {}, forks: {}, size: {}, commits: {}, importance: {}){}{}{}  {}",
+                    repo.full_name(), lang_str, repo.created_at, repo.pushed_at, repo.stargazers_count, repo.forks_count, repo.size, repo.commits, repo.importance_score, topics, authorship, readme_indicator, repo.url
                 )
             })
             .collect::<Vec<_>>()
@@ -221,15 +265,17 @@ impl ResumeAgent {
             - Language diversity (vary the tech stack)\n\
             - Relevance to job requirements: {}\n\
             - Project maturity (complete, not WIP)\n\
-            - Star count and forks (community engagement)\n\n\
+            - Star count and forks (community engagement)\n\
+            - Authorship (prefer repositories where the candidate wrote most of the commits)\n\n\
             REPOSITORIES:\n\
             {}\n\n\
+            Give each repository's name exactly as listed, owner included.\n\n\
             Respond ONLY with valid JSON in this exact format:\n\
             {{\n\
               \"ranked_repositories\": [\n\
                 {{\n\
                   \"rank\": 1,\n\
-                  \"name\": \"project-name\",\n\
+                  \"name\": \"owner/project-name\",\n\
                   \"reasoning\": \"Why this is a good choice for the resume\"\n\
                 }},\n\
                 ...\n\
@@ -244,74 +290,36 @@ impl ResumeAgent {
             repos_list
         );
 
-        debug!("ranking prompt length: {} characters", prompt.len());
-
-        let client = reqwest::Client::new();
-        let api_key = self.api_key.clone();
-        let endpoint = self.endpoint.clone();
-        let model = self.model.clone();
-
-        let response = (|| async {
-            let request_body = json!({
-                "contents": [{"parts": [{"text": prompt}]}],
-                "generationConfig": {
-                    "responseMimeType": "application/json",
-                    "responseJsonSchema": {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "ranked_repositories": {
+                    "type": "array",
+                    "items": {
                         "type": "object",
                         "properties": {
-                            "ranked_repositories": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "rank": { "type": "integer" },
-                                        "name": { "type": "string" },
-                                        "reasoning": { "type": "string" }
-                                    },
-                                    "required": ["rank", "name", "reasoning"]
-                                }
-                            }
+                            "rank": { "type": "integer" },
+                            "name": { "type": "string" },
+                            "reasoning": { "type": "string" }
                         },
-                        "required": ["ranked_repositories"]
+                        "required": ["rank", "name", "reasoning"]
                     }
                 }
-            });
-
-            let url = format!(
-                "{}/{}:generateContent?key={}",
-                endpoint.trim_end_matches('/'),
-                model,
-                api_key
-            );
-
-            let response = client.post(&url).json(&request_body).send().await?;
-
-            let status = response.status();
-            if !status.is_success() {
-                let error_body = response.text().await?;
-                return Err(eyre!(
-                    "Repository ranking failed ({}): {}",
-                    status,
-                    error_body
-                ));
-            }
-
-            Ok(response)
-        })
-        .retry(ExponentialBuilder::default())
-        .await?;
-
-        let body: serde_json::Value = response.json().await?;
-
-        let ranked = body
-            .get("candidates")
-            .and_then(|c| c.get(0))
-            .and_then(|c| c.get("content"))
-            .and_then(|c| c.get("parts"))
-            .and_then(|p| p.get(0))
-            .and_then(|p| p.get("text"))
-            .and_then(|t| t.as_str())
-            .ok_or_else(|| eyre!("invalid ranking response structure"))?;
+            },
+            "required": ["ranked_repositories"]
+        });
+
+        let ranked = self
+            .complete(
+                "repository ranking",
+                &LLMRequest {
+                    system: None,
+                    prompt: &prompt,
+                    schema_name: "ranked_repositories",
+                    schema: &schema,
+                },
+            )
+            .await?;
 
         let trimmed = ranked.trim();
         let json_start = trimmed
@@ -350,18 +358,21 @@ impl ResumeAgent {
 
     pub async fn generate_resume_content(
         &self,
-        resume_config: &ResumeConfig,
-        job_description: &JobDescription,
-        github_repos: Vec<GitHubRepoData>,
-        language: &ResumeLanguage,
+        input: &GenerationInput<'_>,
     ) -> Result<LLMResumeOutput> {
         info!("generating resume content using LLM with structured output");
 
-        let prompt = self.build_prompt(resume_config, job_description, &github_repos, language);
+        let prompt = self.build_prompt(input);
 
-        let response = self.call_gemini_api(&prompt).await?;
+        let response = self.call_llm("resume generation", &prompt).await?;
 
         let output = self.parse_response(&response)?;
+        let mut output =
+            HallucinationGuard::new(self.guard, input.resume_config, input.github_repos)
+                .with_contributions(input.contributions)
+                .with_fingerprints(input.fingerprints)
+                .verify(output);
+        self.annotate_projects(&mut output, input.github_repos);
 
         info!("successfully generated resume content");
         debug!("LLM output: {:#?}", output);
@@ -369,16 +380,311 @@ impl ResumeAgent {
         Ok(output)
     }
 
-    fn build_prompt(
+    /// Asks the LLM to condense already generated content so the resume
+    /// fits in fewer pages, keeping its structure, facts and links.
+    pub async fn shorten_resume_content(
         &self,
-        resume_config: &ResumeConfig,
-        job_description: &JobDescription,
-        github_repos: &[GitHubRepoData],
-        language: &ResumeLanguage,
-    ) -> String {
-        let repos_list = github_repos
+        input: &GenerationInput<'_>,
+        output: &LLMResumeOutput,
+        pages: usize,
+        max_pages: usize,
+    ) -> Result<LLMResumeOutput> {
+        let job_description = input.job_description;
+        info!(
+            "asking the LLM to shorten the resume ({} pages, max {})",
+            pages, max_pages
+        );
+
+        let prompt = format!(
+            "The resume content below renders to {} pages, but it must fit in {}. \
+            Shorten it for a {} role:\n\
+            - Make every bullet point more concise, without losing metrics or technologies\n\
+            - Remove the bullet points least relevant to the job\n\
+            - Keep every title, company, institution, date and link exactly as they are\n\
+            - Do not invent anything new\n\
+            - Keep writing in {}\n\n\
+            Job requirements:\n{}\n\n\
+            Current resume content (JSON):\n{}",
+            pages,
+            max_pages,
+            job_description.title,
+            input.language.name(),
+            job_description.requirements,
+            serde_json::to_string_pretty(output)?
+        );
+
+        let response = self.call_llm("page fitting", &prompt).await?;
+        let shortened = self.parse_response(&response)?;
+        let mut shortened =
+            HallucinationGuard::new(self.guard, input.resume_config, input.github_repos)
+                .with_contributions(input.contributions)
+                .with_fingerprints(input.fingerprints)
+                .verify(shortened);
+        self.annotate_projects(&mut shortened, input.github_repos);
+
+        debug!("shortened LLM output: {:#?}", shortened);
+        Ok(shortened)
+    }
+
+    /// Asks the LLM to rewrite one section or entry of already generated
+    /// content, optionally following free-text `feedback`. The rest of the
+    /// content is sent as context and kept unchanged.
+    pub async fn regenerate(
+        &self,
+        input: &GenerationInput<'_>,
+        output: &LLMResumeOutput,
+        target: OutputTarget,
+        feedback: Option<&str>,
+    ) -> Result<LLMResumeOutput> {
+        let section = target.section();
+        if let OutputTarget::Entry(_, index) = target
+            && index >= section.count(output)
+        {
+            return Err(eyre!(
+                "{} has no entry #{}",
+                section.label().to_lowercase(),
+                index + 1
+            ));
+        }
+
+        info!("regenerating {}", target.describe(output));
+
+        let properties = &resume_schema()["properties"];
+        let (task, schema) = match target {
+            OutputTarget::Section(_) => (
+                format!(
+                    "Rewrite ONLY the `{}` section. Every other section stays as it is. \
+                    Respond with a JSON object holding just that section.",
+                    section.key()
+                ),
+                json!({
+                    "type": "object",
+                    "properties": { section.key(): properties[section.key()] },
+                    "required": [section.key()]
+                }),
+            ),
+            OutputTarget::Entry(_, index) => (
+                format!(
+                    "Rewrite ONLY entry #{} of `{}` (\"{}\"). The rest of the resume stays as it is. \
+                    Keep the entry's facts, dates and links. Respond with that single entry as JSON.",
+                    index + 1,
+                    section.key(),
+                    section.title(output, index)
+                ),
+                properties[section.key()]["items"].clone(),
+            ),
+        };
+
+        let feedback = match feedback {
+            Some(feedback) => format!(
+                "Feedback on the current version, to address in the rewrite:\n{}",
+                feedback
+            ),
+            None => {
+                String::from("Rework the wording and pick the details most relevant to the job.")
+            }
+        };
+
+        let prompt = format!(
+            "{}\n\n## CURRENT RESUME CONTENT\n\n{}\n\n## TASK\n\n{}\n\n{}",
+            self.build_prompt(input),
+            serde_json::to_string_pretty(output)?,
+            task,
+            feedback
+        );
+
+        let response = self
+            .complete(
+                "regeneration",
+                &LLMRequest {
+                    system: Some(SYSTEM_PROMPT),
+                    prompt: &prompt,
+                    schema_name: "resume_part",
+                    schema: &schema,
+                },
+            )
+            .await?;
+
+        let trimmed = response.trim();
+        let json_start = trimmed
+            .find('{')
+            .ok_or_else(|| eyre!("no JSON object found in regeneration response"))?;
+        let json_end = trimmed
+            .rfind('}')
+            .ok_or_else(|| eyre!("malformed JSON in regeneration response"))?;
+        let mut part: serde_json::Value = serde_json::from_str(&trimmed[json_start..=json_end])?;
+
+        let mut value = serde_json::to_value(output)?;
+        match target {
+            OutputTarget::Section(_) => {
+                value[section.key()] = part
+                    .get_mut(section.key())
+                    .filter(|list| list.is_array())
+                    .map(serde_json::Value::take)
+                    .ok_or_else(|| {
+                        eyre!("regeneration response has no `{}` list", section.key())
+                    })?;
+            }
+            OutputTarget::Entry(_, index) => value[section.key()][index] = part,
+        }
+        let output: LLMResumeOutput = serde_json::from_value(value)
+            .map_err(|e| eyre!("failed to parse regenerated content: {}", e))?;
+
+        debug!("regenerated LLM output: {:#?}", output);
+        let mut output =
+            HallucinationGuard::new(self.guard, input.resume_config, input.github_repos)
+                .with_contributions(input.contributions)
+                .with_fingerprints(input.fingerprints)
+                .verify(output);
+        self.annotate_projects(&mut output, input.github_repos);
+        Ok(output)
+    }
+
+    /// Labels projects with the host of their repository, and marks those
+    /// linking to unpublished repositories, or private ones unless private
+    /// links are to be shown, so their links are left off the resume.
+    fn annotate_projects(&self, output: &mut LLMResumeOutput, github_repos: &[RepoData]) {
+        for project in &mut output.projects {
+            let link = repo_key(&project.link);
+            let Some(repo) = github_repos.iter().find(|repo| repo_key(&repo.url) == link) else {
+                continue;
+            };
+
+            project.forge = repo.forge.clone();
+            if repo.unpublished {
+                project.hide_link = true;
+            } else if repo.private && self.private_links != PrivateLinks::Show {
+                info!("hiding link to private repository: {}", project.title);
+                project.hide_link = true;
+            }
+        }
+    }
+
+    /// Writes a cover letter for the job from the generated resume content,
+    /// the selected repositories and the candidate's context.
+    pub async fn generate_cover_letter(
+        &self,
+        input: &GenerationInput<'_>,
+        output: &LLMResumeOutput,
+    ) -> Result<CoverLetter> {
+        info!("generating cover letter using LLM");
+
+        let resume_config = input.resume_config;
+        let job_description = input.job_description;
+        let repos_list = input
+            .github_repos
             .iter()
-            .map(|repo| {
+            .map(|repo| format!("- {} ({})", repo.name, repo.url))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let prompt = format!(
+            "Write a cover letter from {} for the {} role at {}.\n\n\
+            JOB DESCRIPTION:\n{}\n\n\
+            REQUIREMENTS:\n{}\n\n\
+            CANDIDATE RESUME (JSON):\n{}\n\n\
+            SELECTED REPOSITORIES:\n{}\n\n\
+            ADDITIONAL CONTEXT:\n\
+            Experience: {}\n\
+            Skills: {}\n\
+            Education: {}\n\n\
+            GUIDELINES:\n\
+            - Write in {}\n\
+            - 3 to 4 paragraphs, under 350 words in total\n\
+            - Open with the role and why the candidate fits it, connect 2-3 specific projects or \
+            experiences from the resume to the requirements, and close with a call to action\n\
+            - Only mention facts present in the resume or the context: never invent employers, \
+            metrics or technologies\n\
+            - Plain text only: no markdown and no placeholders such as [Company]\n\
+            - greeting: the salutation line, addressing the company by name when known\n\
+            - closing: the sign-off phrase only, without the candidate's name",
+            resume_config.full_name,
+            job_description.title,
+            job_description
+                .company
+                .as_deref()
+                .unwrap_or("the target company"),
+            job_description.description,
+            job_description.requirements,
+            serde_json::to_string_pretty(output)?,
+            repos_list,
+            resume_config
+                .experience_context
+                .as_deref()
+                .unwrap_or("not provided"),
+            resume_config
+                .skills_context
+                .as_deref()
+                .unwrap_or("not provided"),
+            resume_config
+                .education_context
+                .as_deref()
+                .unwrap_or("not provided"),
+            input.language.name()
+        );
+
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "greeting": {
+                    "type": "string",
+                    "description": "Salutation line, e.g. 'Dear Hiring Manager,'"
+                },
+                "paragraphs": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Body paragraphs, in order"
+                },
+                "closing": {
+                    "type": "string",
+                    "description": "Sign-off phrase without the name, e.g. 'Sincerely,'"
+                }
+            },
+            "required": ["greeting", "paragraphs", "closing"]
+        });
+
+        let response = self
+            .complete(
+                "cover letter generation",
+                &LLMRequest {
+                    system: None,
+                    prompt: &prompt,
+                    schema_name: "cover_letter",
+                    schema: &schema,
+                },
+            )
+            .await?;
+
+        let trimmed = response.trim();
+        let json_start = trimmed
+            .find('{')
+            .ok_or_else(|| eyre!("no JSON object found in cover letter response"))?;
+        let json_end = trimmed
+            .rfind('}')
+            .ok_or_else(|| eyre!("malformed JSON in cover letter response"))?;
+        let letter: CoverLetter = serde_json::from_str(&trimmed[json_start..=json_end])
+            .map_err(|e| eyre!("failed to parse cover letter response: {}", e))?;
+
+        debug!("cover letter: {:#?}", letter);
+        Ok(letter)
+    }
+
+    fn build_prompt(&self, input: &GenerationInput<'_>) -> String {
+        let resume_config = input.resume_config;
+        let job_description = input.job_description;
+
+        let readmes: Vec<Option<&str>> = input
+            .github_repos
+            .iter()
+            .map(|repo| repo.readme.as_deref())
+            .collect();
+        let readme_snippets = readme_snippets(&readmes, &self.readme);
+
+        let repos_list = input
+            .github_repos
+            .iter()
+            .zip(readme_snippets)
+            .map(|(repo, readme)| {
                 let lang_str: String = repo.languages.as_ref().map(|langs|
                     langs.languages
                         .iter()
@@ -392,20 +698,25 @@ impl ResumeAgent {
                         .join(", ")
                 ).unwrap_or_else(|| "Unknown".to_string());
 
-                let readme_snippet = if let Some(readme_content) = repo.readme.as_ref() {
+                let readme_snippet = if let Some(readme_content) = readme {
                     format!("\n  README:\n```\n{}\n```", readme_content)
                 } else {
                     String::new()
                 };
 
+                let authorship = repo.authorship.as_ref().map(|a| format!(", authored by the candidate: {}", a)).unwrap_or_default();
+                let topics = if repo.topics.is_empty() { String::new() } else { format!(", topics: {}", repo.topics.join(", ")) };
+                let stack = input.fingerprints.iter().find(|f| f.repository == repo.url).map(|f| format!("\n  STACK: {}", f)).unwrap_or_default();
                 format!(
-                    "- {} [{}] (created: {}, last updated: {}, stars: {}, forks: {}, size: {}, commits: {}, importance: {}) - {}{}",
-                    repo.name, lang_str, repo.created_at, repo.pushed_at, repo.stargazers_count, repo.forks_count, repo.size, repo.commits, repo.importance_score, repo.url, readme_snippet
+                    "- {} [{}] (created: {}, last updated: {}, stars: {}, forks: {}, size: {}, commits: {}, importance: {}{}{}) - {}{}{}",
+                    repo.name, lang_str, repo.created_at, repo.pushed_at, repo.stargazers_count, repo.forks_count, repo.size, repo.commits, repo.importance_score, topics, authorship, repo.url, stack, readme_snippet
                 )
             })
             .collect::<Vec<_>>()
             .join("\n");
 
+        let contributions_list = contributions_list(input.contributions);
+
         let education_context = resume_config
             .education_context
             .as_deref()
@@ -433,166 +744,91 @@ impl ResumeAgent {
             )
             .replace("{job_description}", &job_description.description)
             .replace("{github_repos}", &repos_list)
+            .replace("{contributions}", &contributions_list)
             .replace("{education_context}", education_context)
             .replace("{experience_context}", experience_context)
             .replace("{skills_context}", skills_context)
-            .replace(
-                "{language}",
-                match language {
-                    ResumeLanguage::English => "English",
-                    ResumeLanguage::Portuguese => "Portuguese",
-                },
-            )
+            .replace("{language}", input.language.name())
     }
 
-    async fn call_gemini_api(&self, prompt: &str) -> Result<String> {
+    async fn call_llm(&self, stage: &str, prompt: &str) -> Result<String> {
         info!(
-            "calling Gemini API with structured output (model: {}, max retries: {})",
-            self.model, self.max_retries
+            "calling {} API with structured output (model: {}, max retries: {})",
+            self.provider.name(),
+            self.provider.model(),
+            self.max_retries
         );
-        debug!("prompt length: {} characters", prompt.len());
-
-        let client = reqwest::Client::new();
-        let api_key = self.api_key.clone();
-        let endpoint = self.endpoint.clone();
-        let model = self.model.clone();
 
-        let response = (|| async {
-            let request_body = json!({
-                "contents": [
-                    {
-                        "parts": [
-                            {
-                                "text": prompt
-                            }
-                        ]
-                    }
-                ],
-                "systemInstruction": {
-                    "parts": [
-                        {
-                            "text": SYSTEM_PROMPT
-                        }
-                    ]
-                },
-                "generationConfig": {
-                    "responseMimeType": "application/json",
-                    "responseJsonSchema": {
-                        "type": "object",
-                        "properties": {
-                            "skills_by_category": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "category": {
-                                            "type": "string",
-                                            "description": "Technical skill category (e.g., Back-end, Front-end)"
-                                        },
-                                        "items": {
-                                            "type": "array",
-                                            "items": { "type": "string" },
-                                            "description": "List of specific skills in this category"
-                                        }
-                                    },
-                                    "required": ["category", "items"]
-                                }
-                            },
-                            "projects": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "title": {
-                                            "type": "string",
-                                            "description": "Project name in format: 'Project Name (Technology/Language)'"
-                                        },
-                                        "link": {
-                                            "type": "string",
-                                            "description": "GitHub repository URL"
-                                        },
-                                        "items": {
-                                            "type": "array",
-                                            "items": { "type": "string" },
-                                            "description": "Single brief line (max 15 words) describing core purpose or key feature"
-                                        }
-                                    },
-                                    "required": ["title", "link", "items"]
-                                }
-                            },
-                            "education": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "institution": { "type": "string" },
-                                        "degree": { "type": "string" },
-                                        "location": { "type": "string" },
-                                        "date": { "type": "string" },
-                                        "accomplishments": {
-                                            "type": "array",
-                                            "items": { "type": "string" }
-                                        }
-                                    },
-                                    "required": ["institution", "degree", "location", "date", "accomplishments"]
-                                }
-                            },
-                            "experience": {
-                                "type": "array",
-                                "items": {
-                                    "type": "object",
-                                    "properties": {
-                                        "company": { "type": "string" },
-                                        "position": { "type": "string" },
-                                        "location": { "type": "string" },
-                                        "date": { "type": "string" },
-                                        "accomplishments": {
-                                            "type": "array",
-                                            "items": { "type": "string" }
-                                        }
-                                    },
-                                    "required": ["company", "position", "location", "date", "accomplishments"]
-                                }
-                            }
-                        },
-                        "required": ["skills_by_category", "projects", "education", "experience"]
-                    }
-                }
-            });
+        self.complete(
+            stage,
+            &LLMRequest {
+                system: Some(SYSTEM_PROMPT),
+                prompt,
+                schema_name: "resume_content",
+                schema: &resume_schema(),
+            },
+        )
+        .await
+    }
 
-            let url = format!(
-                "{}/{}:generateContent?key={}",
-                endpoint.trim_end_matches('/'),
-                model,
-                api_key
-            );
+    /// Sends a request through the configured provider, retrying failed calls,
+    /// and returns the raw generated text.
+    async fn complete(&self, stage: &str, request: &LLMRequest<'_>) -> Result<String> {
+        let input_tokens =
+            estimate_tokens(request.prompt) + request.system.map_or(0, estimate_tokens);
+        debug!("{} prompt: about {} tokens", stage, input_tokens);
+        let reservation = self.usage.reserve(
+            stage,
+            input_tokens as u64,
+            self.provider.max_output_tokens().map(u64::from),
+        )?;
 
-            let response = client.post(&url).json(&request_body).send().await?;
+        let response = (|| async {
+            let response = self.provider.request(&self.client, request).send().await?;
 
             let status = response.status();
             if !status.is_success() {
                 let error_body = response.text().await?;
-                return Err(eyre!("Gemini API error ({}): {}", status, error_body));
+                return Err(eyre!(
+                    "{} API error during {} ({}): {}",
+                    self.provider.name(),
+                    stage,
+                    status,
+                    error_body
+                ));
             }
 
             Ok(response)
         })
-        .retry(ExponentialBuilder::default())
+        .retry(ExponentialBuilder::default().with_max_times(self.max_retries as usize))
         .await?;
 
         let body: serde_json::Value = response.json().await?;
+        let text = self.provider.extract_text(&body);
+
+        let (tokens, estimated) = match self.provider.extract_usage(&body) {
+            Some(tokens) => (tokens, false),
+            None => (
+                TokenUsage {
+                    input: input_tokens as u64,
+                    output: text.as_deref().map_or(0, estimate_tokens) as u64,
+                },
+                true,
+            ),
+        };
+        debug!(
+            "{} used {} input and {} output tokens",
+            stage, tokens.input, tokens.output
+        );
+        reservation.record(tokens, estimated);
 
-        let content = body
-            .get("candidates")
-            .and_then(|c| c.get(0))
-            .and_then(|c| c.get("content"))
-            .and_then(|c| c.get("parts"))
-            .and_then(|p| p.get(0))
-            .and_then(|p| p.get("text"))
-            .and_then(|t| t.as_str())
-            .ok_or_else(|| eyre!("invalid Gemini API response structure"))?;
-
-        Ok(content.to_string())
+        text.ok_or_else(|| {
+            eyre!(
+                "invalid {} response structure for {}",
+                self.provider.name(),
+                stage
+            )
+        })
     }
 
     fn parse_response(&self, response: &str) -> Result<LLMResumeOutput> {
@@ -622,6 +858,153 @@ impl ResumeAgent {
     }
 }
 
+/// Merged pull requests grouped by upstream repository, as listed in the
+/// generation prompt.
+fn contributions_list(contributions: &[ExternalContribution]) -> String {
+    if contributions.is_empty() {
+        return "No merged pull requests to other projects".to_string();
+    }
+
+    let mut repositories: Vec<&str> = Vec::new();
+    for contribution in contributions {
+        if !repositories.contains(&contribution.repository.as_str()) {
+            repositories.push(&contribution.repository);
+        }
+    }
+
+    repositories
+        .into_iter()
+        .map(|repository| {
+            let pull_requests: Vec<&ExternalContribution> = contributions
+                .iter()
+                .filter(|contribution| contribution.repository == repository)
+                .collect();
+            let upstream = pull_requests[0];
+
+            format!(
+                "- {} [{}] (stars: {}, merged pull requests: {}) - {}\n{}",
+                upstream.repository,
+                upstream.language.as_deref().unwrap_or("Unknown"),
+                upstream.stars,
+                pull_requests.len(),
+                upstream.repository_url,
+                pull_requests
+                    .iter()
+                    .map(|pr| format!("  - {}: {}", pr.merged_at, pr.title))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// JSON schema of `LLMResumeOutput`, sent with every resume generation call.
+fn resume_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "skills_by_category": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "category": {
+                            "type": "string",
+                            "description": "Technical skill category (e.g., Back-end, Front-end)"
+                        },
+                        "items": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "List of specific skills in this category"
+                        }
+                    },
+                    "required": ["category", "items"]
+                }
+            },
+            "projects": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "title": {
+                            "type": "string",
+                            "description": "Project name in format: 'Project Name (Technology/Language)'"
+                        },
+                        "link": {
+                            "type": "string",
+                            "description": "GitHub repository URL"
+                        },
+                        "items": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Single brief line (max 15 words) describing core purpose or key feature"
+                        }
+                    },
+                    "required": ["title", "link", "items"]
+                }
+            },
+            "education": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "institution": { "type": "string" },
+                        "degree": { "type": "string" },
+                        "location": { "type": "string" },
+                        "date": { "type": "string" },
+                        "accomplishments": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "required": ["institution", "degree", "location", "date", "accomplishments"]
+                }
+            },
+            "experience": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "company": { "type": "string" },
+                        "position": { "type": "string" },
+                        "location": { "type": "string" },
+                        "date": { "type": "string" },
+                        "accomplishments": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "required": ["company", "position", "location", "date", "accomplishments"]
+                }
+            },
+            "contributions": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "project": {
+                            "type": "string",
+                            "description": "Upstream repository as 'owner/name'"
+                        },
+                        "link": {
+                            "type": "string",
+                            "description": "GitHub URL of the upstream repository"
+                        },
+                        "items": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "What the merged pull requests changed (max 15 words each)"
+                        }
+                    },
+                    "required": ["project", "link", "items"]
+                }
+            }
+        },
+        "required": ["skills_by_category", "projects", "education", "experience", "contributions"]
+    })
+}
+
 pub fn resume_output_to_resume_items(
     output: &LLMResumeOutput,
 ) -> (
@@ -629,6 +1012,7 @@ pub fn resume_output_to_resume_items(
     Vec<ResumeItem>,
     Vec<ResumeItem>,
     Vec<ResumeItem>,
+    Vec<ResumeItem>,
 ) {
     let skills = ResumeItem {
         title: None,
@@ -649,9 +1033,14 @@ pub fn resume_output_to_resume_items(
         .map(|proj| ResumeItem {
             title: Some(proj.title.clone()),
             date: None,
-            location: Some("GitHub".to_string()),
+            // from the link for projects matching no scraped repository, or
+            // content generated before projects were labeled with their host
+            location: proj
+                .forge
+                .clone()
+                .or_else(|| (!proj.hide_link).then(|| host_label(&proj.link))),
             description: None,
-            link: Some(proj.link.clone()),
+            link: (!proj.hide_link).then(|| proj.link.clone()),
             items: proj.items.clone(),
         })
         .collect();
@@ -682,5 +1071,18 @@ pub fn resume_output_to_resume_items(
         })
         .collect();
 
-    (skills, experience, projects, education)
+    let contributions: Vec<ResumeItem> = output
+        .contributions
+        .iter()
+        .map(|contribution| ResumeItem {
+            title: Some(contribution.project.clone()),
+            date: None,
+            location: Some("GitHub".to_string()),
+            description: None,
+            link: Some(contribution.link.clone()),
+            items: contribution.items.clone(),
+        })
+        .collect();
+
+    (skills, experience, projects, education, contributions)
 }
@@ -1 +1,5 @@
 pub mod agent;
+pub mod provider;
+pub mod readme;
+pub mod section;
+pub mod usage;
@@ -0,0 +1,353 @@
+use eyre::{Result, eyre};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::chat::usage::TokenUsage;
+use crate::utils::config::LLMConfig;
+
+/// A single structured-output completion request, independent of the backend.
+pub struct LLMRequest<'a> {
+    pub system: Option<&'a str>,
+    pub prompt: &'a str,
+    pub schema_name: &'a str,
+    pub schema: &'a Value,
+}
+
+/// An LLM backend capable of answering a prompt with JSON matching a schema.
+///
+/// Providers only know how to shape the HTTP request and where the generated
+/// text lives in the response body; sending and retrying is left to the agent.
+pub trait LLMProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn model(&self) -> &str;
+
+    fn request(&self, client: &reqwest::Client, request: &LLMRequest) -> RequestBuilder;
+
+    fn extract_text(&self, body: &Value) -> Option<String>;
+
+    /// Most tokens a reply may hold, if the request sets a limit.
+    fn max_output_tokens(&self) -> Option<u32> {
+        None
+    }
+
+    /// Token counts the response reports, if any.
+    fn extract_usage(&self, body: &Value) -> Option<TokenUsage>;
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    Gemini,
+    #[serde(alias = "openai-compatible")]
+    OpenAI,
+    Anthropic,
+    Ollama,
+}
+
+impl ProviderKind {
+    pub fn default_endpoint(&self) -> &'static str {
+        match self {
+            ProviderKind::Gemini => "https://generativelanguage.googleapis.com/v1beta/models",
+            ProviderKind::OpenAI => "https://api.openai.com/v1",
+            ProviderKind::Anthropic => "https://api.anthropic.com/v1",
+            ProviderKind::Ollama => "http://localhost:11434",
+        }
+    }
+
+    /// Model used when none is configured. Only Gemini has one, as the
+    /// other providers' models depend on the account or server.
+    pub fn default_model(&self) -> Option<&'static str> {
+        match self {
+            ProviderKind::Gemini => Some("gemini-3-flash-preview"),
+            ProviderKind::OpenAI | ProviderKind::Anthropic | ProviderKind::Ollama => None,
+        }
+    }
+
+    /// e.g. `anthropic`, as written in the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProviderKind::Gemini => "gemini",
+            ProviderKind::OpenAI => "openai",
+            ProviderKind::Anthropic => "anthropic",
+            ProviderKind::Ollama => "ollama",
+        }
+    }
+
+    fn requires_api_key(&self) -> bool {
+        matches!(self, ProviderKind::Gemini | ProviderKind::Anthropic)
+    }
+}
+
+pub fn provider_from_config(config: &LLMConfig) -> Result<Box<dyn LLMProvider>> {
+    let api_key = config.api_key.clone();
+    if config.provider.requires_api_key() && api_key.is_none() {
+        return Err(eyre!("LLM API key not configured in config.toml"));
+    }
+
+    let endpoint = config
+        .endpoint
+        .clone()
+        .unwrap_or_else(|| config.provider.default_endpoint().to_string())
+        .trim_end_matches('/')
+        .to_string();
+    let model = config.model()?.to_string();
+
+    Ok(match config.provider {
+        ProviderKind::Gemini => Box::new(GeminiProvider {
+            api_key: api_key.unwrap_or_default(),
+            model,
+            endpoint,
+        }),
+        ProviderKind::OpenAI => Box::new(OpenAIProvider {
+            api_key,
+            model,
+            endpoint,
+        }),
+        ProviderKind::Anthropic => Box::new(AnthropicProvider {
+            api_key: api_key.unwrap_or_default(),
+            model,
+            endpoint,
+        }),
+        ProviderKind::Ollama => Box::new(OllamaProvider { model, endpoint }),
+    })
+}
+
+pub struct GeminiProvider {
+    api_key: String,
+    model: String,
+    endpoint: String,
+}
+
+impl LLMProvider for GeminiProvider {
+    fn name(&self) -> &'static str {
+        "Gemini"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn request(&self, client: &reqwest::Client, request: &LLMRequest) -> RequestBuilder {
+        let mut body = json!({
+            "contents": [{"parts": [{"text": request.prompt}]}],
+            "generationConfig": {
+                "responseMimeType": "application/json",
+                "responseJsonSchema": request.schema
+            }
+        });
+
+        if let Some(system) = request.system {
+            body["systemInstruction"] = json!({"parts": [{"text": system}]});
+        }
+
+        client
+            .post(format!(
+                "{}/{}:generateContent?key={}",
+                self.endpoint, self.model, self.api_key
+            ))
+            .json(&body)
+    }
+
+    fn extract_text(&self, body: &Value) -> Option<String> {
+        body.get("candidates")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("content"))
+            .and_then(|c| c.get("parts"))
+            .and_then(|p| p.get(0))
+            .and_then(|p| p.get("text"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+    }
+
+    fn extract_usage(&self, body: &Value) -> Option<TokenUsage> {
+        let usage = body.get("usageMetadata")?;
+        let count = |key: &str| usage.get(key).and_then(|c| c.as_u64()).unwrap_or(0);
+        Some(TokenUsage {
+            input: count("promptTokenCount"),
+            // thinking tokens are billed as output
+            output: count("candidatesTokenCount") + count("thoughtsTokenCount"),
+        })
+    }
+}
+
+/// OpenAI chat-completions API, also spoken by most self-hosted servers
+/// (vLLM, llama.cpp, LM Studio, LiteLLM, ...).
+pub struct OpenAIProvider {
+    api_key: Option<String>,
+    model: String,
+    endpoint: String,
+}
+
+impl LLMProvider for OpenAIProvider {
+    fn name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn request(&self, client: &reqwest::Client, request: &LLMRequest) -> RequestBuilder {
+        let mut messages = Vec::new();
+        if let Some(system) = request.system {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.push(json!({"role": "user", "content": request.prompt}));
+
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {
+                    "name": request.schema_name,
+                    "schema": request.schema
+                }
+            }
+        });
+
+        let req = client
+            .post(format!("{}/chat/completions", self.endpoint))
+            .json(&body);
+
+        match &self.api_key {
+            Some(api_key) => req.bearer_auth(api_key),
+            None => req,
+        }
+    }
+
+    fn extract_text(&self, body: &Value) -> Option<String> {
+        body.get("choices")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("message"))
+            .and_then(|m| m.get("content"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+    }
+
+    fn extract_usage(&self, body: &Value) -> Option<TokenUsage> {
+        let usage = body.get("usage")?;
+        Some(TokenUsage {
+            input: usage.get("prompt_tokens")?.as_u64()?,
+            output: usage.get("completion_tokens")?.as_u64()?,
+        })
+    }
+}
+
+/// Anthropic messages API. Structured output is obtained by forcing a single
+/// tool call whose input schema is the requested schema.
+pub struct AnthropicProvider {
+    api_key: String,
+    model: String,
+    endpoint: String,
+}
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const ANTHROPIC_MAX_TOKENS: u32 = 8192;
+
+impl LLMProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "Anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn max_output_tokens(&self) -> Option<u32> {
+        Some(ANTHROPIC_MAX_TOKENS)
+    }
+
+    fn request(&self, client: &reqwest::Client, request: &LLMRequest) -> RequestBuilder {
+        let mut body = json!({
+            "model": self.model,
+            "max_tokens": ANTHROPIC_MAX_TOKENS,
+            "messages": [{"role": "user", "content": request.prompt}],
+            "tools": [{
+                "name": request.schema_name,
+                "description": "Submit the response as structured JSON",
+                "input_schema": request.schema
+            }],
+            "tool_choice": {"type": "tool", "name": request.schema_name}
+        });
+
+        if let Some(system) = request.system {
+            body["system"] = json!(system);
+        }
+
+        client
+            .post(format!("{}/messages", self.endpoint))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body)
+    }
+
+    fn extract_text(&self, body: &Value) -> Option<String> {
+        body.get("content")
+            .and_then(|c| c.as_array())
+            .and_then(|blocks| {
+                blocks
+                    .iter()
+                    .find(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+            })
+            .and_then(|b| b.get("input"))
+            .map(|input| input.to_string())
+    }
+
+    fn extract_usage(&self, body: &Value) -> Option<TokenUsage> {
+        let usage = body.get("usage")?;
+        Some(TokenUsage {
+            input: usage.get("input_tokens")?.as_u64()?,
+            output: usage.get("output_tokens")?.as_u64()?,
+        })
+    }
+}
+
+pub struct OllamaProvider {
+    model: String,
+    endpoint: String,
+}
+
+impl LLMProvider for OllamaProvider {
+    fn name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn request(&self, client: &reqwest::Client, request: &LLMRequest) -> RequestBuilder {
+        let mut messages = Vec::new();
+        if let Some(system) = request.system {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.push(json!({"role": "user", "content": request.prompt}));
+
+        client
+            .post(format!("{}/api/chat", self.endpoint))
+            .json(&json!({
+                "model": self.model,
+                "messages": messages,
+                "format": request.schema,
+                "stream": false
+            }))
+    }
+
+    fn extract_text(&self, body: &Value) -> Option<String> {
+        body.get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+    }
+
+    fn extract_usage(&self, body: &Value) -> Option<TokenUsage> {
+        Some(TokenUsage {
+            input: body.get("prompt_eval_count")?.as_u64()?,
+            output: body.get("eval_count")?.as_u64()?,
+        })
+    }
+}
@@ -0,0 +1,413 @@
+use std::sync::LazyLock;
+
+use log::info;
+use regex::Regex;
+
+use crate::chat::usage::estimate_tokens;
+use crate::utils::config::ReadmeConfig;
+
+/// Section headings that say nothing about what a project does.
+const BOILERPLATE_HEADINGS: &[&str] = &[
+    "table of contents",
+    "contents",
+    "toc",
+    "license",
+    "licence",
+    "contributing",
+    "contributors",
+    "authors",
+    "acknowledgements",
+    "acknowledgments",
+    "credits",
+    "sponsors",
+    "support",
+    "code of conduct",
+    "changelog",
+];
+
+/// Stands in for a code block over the line limit.
+const CODE_OMITTED: &str = "(code example omitted)";
+
+/// Headings introducing a table of contents, whose section is dropped.
+const TOC_HEADINGS: &[&str] = &["table of contents", "contents", "toc"];
+
+static COMMENTS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").expect("invalid comment regex"));
+static BADGES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[!\[[^\]]*\]\([^)]*\)\]\([^)]*\)").expect("invalid badge regex")
+});
+static IMAGES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"!\[[^\]]*\](\([^)]*\)|\[[^\]]*\])").expect("invalid image regex")
+});
+static HTML: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)</?(a|abbr|b|br|center|code|details|div|em|h[1-6]|hr|i|img|kbd|li|ol|p|picture|pre|source|span|strong|sub|summary|sup|table|tbody|td|th|thead|tr|u|ul|video)\b[^>]*>",
+    )
+    .expect("invalid HTML regex")
+});
+static LINK_DEFINITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\[[^\]]+\]:\s*\S+").expect("invalid link definition regex"));
+static ANCHOR_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*([-*+]|\d+\.)\s*\[[^\]]*\]\(#[^)]*\)\s*$").expect("invalid anchor link regex")
+});
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.*?)(\s+#+)?\s*$").expect("invalid heading regex"));
+
+/// A README stripped of what doesn't describe the project: badges, images,
+/// HTML, tables of contents and long code blocks.
+#[derive(Debug)]
+pub struct Readme {
+    /// First paragraph of prose
+    pub description: Option<String>,
+    /// Section headings, boilerplate ones (license, contributing...) left out
+    pub headings: Vec<String>,
+    /// Everything after the description
+    pub body: String,
+}
+
+impl Readme {
+    /// Normalizes a Markdown README, dropping code blocks longer than
+    /// `max_code_lines` lines.
+    pub fn parse(readme: &str, max_code_lines: usize) -> Self {
+        let readme = COMMENTS.replace_all(readme, "");
+        let mut lines: Vec<String> = Vec::new();
+        let mut headings = Vec::new();
+        let mut fence: Option<(&str, Vec<&str>)> = None;
+        // level of the table of contents heading being skipped
+        let mut toc: Option<usize> = None;
+
+        for line in readme.lines() {
+            let trimmed = line.trim_start();
+            if let Some((marker, code)) = fence.as_mut() {
+                code.push(line);
+                if trimmed.starts_with(*marker) {
+                    // the fences themselves aren't counted
+                    if code.len() - 2 <= max_code_lines {
+                        lines.extend(code.iter().map(|line| line.to_string()));
+                    } else {
+                        lines.push(CODE_OMITTED.to_string());
+                    }
+                    fence = None;
+                }
+                continue;
+            }
+
+            if let Some(captures) = HEADING.captures(line) {
+                let level = captures[1].len();
+                if toc.is_some_and(|toc| level > toc) {
+                    continue;
+                }
+                toc = None;
+                let text = HTML.replace_all(&captures[2], "").trim().to_string();
+                let lowercase = text.to_lowercase();
+                if TOC_HEADINGS.contains(&lowercase.as_str()) {
+                    toc = Some(level);
+                    continue;
+                }
+                if level > 1
+                    && !text.is_empty()
+                    && !BOILERPLATE_HEADINGS.contains(&lowercase.as_str())
+                {
+                    headings.push(text);
+                }
+            } else if toc.is_some() {
+                continue;
+            }
+
+            if let Some(marker) = fence_marker(line) {
+                fence = Some((marker, vec![line]));
+                continue;
+            }
+
+            if LINK_DEFINITION.is_match(line) || ANCHOR_ITEM.is_match(line) {
+                continue;
+            }
+
+            let stripped = BADGES.replace_all(line, "");
+            let stripped = IMAGES.replace_all(&stripped, "");
+            let stripped = HTML.replace_all(&stripped, "");
+            let stripped = stripped.trim_end();
+            // lines that held nothing but badges or markup
+            if stripped.trim().is_empty() && !line.trim().is_empty() {
+                continue;
+            }
+            lines.push(stripped.to_string());
+        }
+        // an unclosed fence runs to the end of the file
+        if let Some((_, code)) = fence {
+            if code.len() - 1 <= max_code_lines {
+                lines.extend(code.iter().map(|line| line.to_string()));
+            } else {
+                lines.push(CODE_OMITTED.to_string());
+            }
+        }
+
+        // the project's title, already known from the repository name
+        let mut rest = lines
+            .iter()
+            .map(String::as_str)
+            .skip_while(|line| line.trim().is_empty())
+            .peekable();
+        if rest
+            .peek()
+            .and_then(|line| HEADING.captures(line))
+            .is_some_and(|captures| captures[1].len() == 1)
+        {
+            rest.next();
+        }
+        let mut rest = rest.skip_while(|line| line.trim().is_empty()).peekable();
+
+        let mut paragraph: Vec<&str> = Vec::new();
+        while let Some(line) = rest.next_if(|line| {
+            !line.trim().is_empty()
+                && !HEADING.is_match(line)
+                && fence_marker(line).is_none()
+                && *line != CODE_OMITTED
+        }) {
+            paragraph.push(line.trim().trim_start_matches('>').trim());
+        }
+
+        let mut body: Vec<&str> = Vec::new();
+        for line in rest {
+            // collapse runs of blank lines
+            if line.is_empty() && body.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+            body.push(line);
+        }
+        while body.last().is_some_and(|last| last.is_empty()) {
+            body.pop();
+        }
+
+        Readme {
+            description: Some(paragraph.join(" ")).filter(|description| !description.is_empty()),
+            headings,
+            body: body.join("\n"),
+        }
+    }
+
+    /// The description and body, if they fit in `budget` tokens. Otherwise
+    /// the description and section headings, followed by as much of the
+    /// body as fits.
+    pub fn render(&self, budget: usize) -> String {
+        let full = match &self.description {
+            Some(description) if self.body.is_empty() => description.clone(),
+            Some(description) => format!("{}\n\n{}", description, self.body),
+            None => self.body.clone(),
+        };
+        if estimate_tokens(&full) <= budget {
+            return full;
+        }
+
+        let mut summary = self.description.clone().unwrap_or_default();
+        if !self.headings.is_empty() {
+            if !summary.is_empty() {
+                summary.push('\n');
+            }
+            summary.push_str(&format!("Sections: {}", self.headings.join(", ")));
+        }
+        let summary = truncate(&summary, budget);
+        let remaining = budget.saturating_sub(estimate_tokens(&summary) + 1);
+        if remaining == 0 {
+            return summary;
+        }
+
+        let body = truncate(&self.body, remaining);
+        if summary.is_empty() {
+            body
+        } else {
+            format!("{}\n\n{}", summary, body)
+        }
+    }
+}
+
+/// The "```" or "~~~" opening a fenced code block on `line`.
+fn fence_marker(line: &str) -> Option<&'static str> {
+    let trimmed = line.trim_start();
+    ["```", "~~~"]
+        .into_iter()
+        .find(|marker| trimmed.starts_with(marker))
+}
+
+/// Cuts `text` down to about `budget` tokens, at a line break if there is
+/// one, marking the cut.
+fn truncate(text: &str, budget: usize) -> String {
+    const MARKER: &str = "\n[...]";
+    if estimate_tokens(text) <= budget {
+        return text.to_string();
+    }
+
+    let chars = (budget * 4).saturating_sub(MARKER.len());
+    let end = text
+        .char_indices()
+        .nth(chars)
+        .map_or(text.len(), |(index, _)| index);
+    let cut = match text[..end].rfind('\n') {
+        Some(line_end) if line_end > 0 => &text[..line_end],
+        _ => &text[..end],
+    };
+    if cut.trim().is_empty() {
+        return String::new();
+    }
+
+    format!("{}{}", cut.trim_end(), MARKER)
+}
+
+/// Token budgets within `total` for texts of the given sizes: texts smaller
+/// than an even share keep their size and leave the rest to larger ones.
+fn share_budget(sizes: &[usize], total: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&index| sizes[index]);
+
+    let mut budgets = vec![0; sizes.len()];
+    let mut remaining = total;
+    for (position, &index) in order.iter().enumerate() {
+        let share = remaining / (sizes.len() - position);
+        budgets[index] = sizes[index].min(share);
+        remaining -= budgets[index];
+    }
+
+    budgets
+}
+
+/// The normalized READMEs of the repositories going into a prompt, each
+/// within `max_tokens` and all together within `max_prompt_tokens`.
+pub fn readme_snippets(readmes: &[Option<&str>], config: &ReadmeConfig) -> Vec<Option<String>> {
+    let readmes: Vec<Option<Readme>> = readmes
+        .iter()
+        .map(|readme| readme.map(|readme| Readme::parse(readme, config.max_code_lines)))
+        .collect();
+    let sizes: Vec<usize> = readmes
+        .iter()
+        .map(|readme| {
+            readme.as_ref().map_or(0, |readme| {
+                estimate_tokens(&readme.render(usize::MAX)).min(config.max_tokens)
+            })
+        })
+        .collect();
+
+    let wanted: usize = sizes.iter().sum();
+    let budgets = if wanted > config.max_prompt_tokens {
+        info!(
+            "READMEs need {} tokens, over the prompt budget of {}; shortening them",
+            wanted, config.max_prompt_tokens
+        );
+        share_budget(&sizes, config.max_prompt_tokens)
+    } else {
+        sizes
+    };
+
+    readmes
+        .iter()
+        .zip(budgets)
+        .map(|(readme, budget)| {
+            Some(readme.as_ref()?.render(budget)).filter(|snippet| !snippet.is_empty())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_badges_html_and_anchor_toc() {
+        let readme = "\
+# project
+
+[![CI](https://ci.example/badge.svg)](https://ci.example) ![logo](logo.png)
+<p align=\"center\"><b>A fast</b> resume generator.</p>
+
+## Table of Contents
+
+- [Install](#install)
+- [Usage](#usage)
+
+## Install
+
+Run `cargo install`.
+
+## License
+
+MIT
+";
+        let parsed = Readme::parse(readme, 15);
+
+        assert_eq!(
+            parsed.description.as_deref(),
+            Some("A fast resume generator.")
+        );
+        assert_eq!(parsed.headings, ["Install"]);
+        assert!(!parsed.body.contains("badge.svg"));
+        assert!(!parsed.body.contains("#install"));
+        assert!(!parsed.body.contains("Table of Contents"));
+        assert!(parsed.body.starts_with("## Install"));
+    }
+
+    #[test]
+    fn drops_code_blocks_over_the_line_limit() {
+        let readme = "\
+Description.
+
+```sh
+cargo run
+```
+
+```rust
+fn one() {}
+fn two() {}
+fn three() {}
+```
+";
+        let parsed = Readme::parse(readme, 2);
+
+        assert!(parsed.body.contains("cargo run"));
+        assert!(!parsed.body.contains("fn one"));
+        assert!(parsed.body.contains("(code example omitted)"));
+    }
+
+    #[test]
+    fn marks_long_unclosed_code_blocks() {
+        let parsed = Readme::parse("Description.\n\n~~~\nline one\nline two\nline three\n", 2);
+
+        assert_eq!(parsed.body, "(code example omitted)");
+    }
+
+    #[test]
+    fn description_stops_at_tilde_fences() {
+        let parsed = Readme::parse("Description.\n~~~\ncode\n~~~\n", 15);
+
+        assert_eq!(parsed.description.as_deref(), Some("Description."));
+        assert_eq!(parsed.body, "~~~\ncode\n~~~");
+    }
+
+    #[test]
+    fn truncates_at_a_line_break() {
+        let text = "first line\nsecond line\nthird line";
+
+        assert_eq!(truncate(text, 100), text);
+        assert_eq!(truncate(text, 6), "first line\n[...]");
+        assert_eq!(truncate(text, 0), "");
+    }
+
+    #[test]
+    fn shares_budget_left_by_small_readmes() {
+        assert_eq!(share_budget(&[100, 10, 1000], 300), [100, 10, 190]);
+        assert_eq!(share_budget(&[500, 600], 300), [150, 150]);
+        assert_eq!(share_budget(&[50, 60], 300), [50, 60]);
+        assert_eq!(share_budget(&[], 300), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn renders_headings_when_over_budget() {
+        let readme = Readme::parse(
+            "Short description.\n\n## Usage\n\nsome text that is long enough to be cut\n",
+            15,
+        );
+
+        assert!(readme.render(usize::MAX).contains("some text"));
+        let rendered = readme.render(12);
+        assert!(rendered.starts_with("Short description.\nSections: Usage"));
+        assert!(estimate_tokens(&rendered) <= 12);
+    }
+}
@@ -0,0 +1,168 @@
+use clap::ValueEnum;
+
+use crate::chat::agent::LLMResumeOutput;
+
+/// A list of entries in the generated resume content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputSection {
+    Skills,
+    Projects,
+    Experience,
+    Education,
+    Contributions,
+}
+
+impl OutputSection {
+    pub const ALL: [OutputSection; 5] = [
+        OutputSection::Skills,
+        OutputSection::Projects,
+        OutputSection::Experience,
+        OutputSection::Education,
+        OutputSection::Contributions,
+    ];
+
+    /// Field of `LLMResumeOutput` (and of the generation schema) holding the section.
+    pub fn key(&self) -> &'static str {
+        match self {
+            OutputSection::Skills => "skills_by_category",
+            OutputSection::Projects => "projects",
+            OutputSection::Experience => "experience",
+            OutputSection::Education => "education",
+            OutputSection::Contributions => "contributions",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputSection::Skills => "Skills",
+            OutputSection::Projects => "Projects",
+            OutputSection::Experience => "Experience",
+            OutputSection::Education => "Education",
+            OutputSection::Contributions => "Open Source Contributions",
+        }
+    }
+
+    pub fn count(&self, output: &LLMResumeOutput) -> usize {
+        match self {
+            OutputSection::Skills => output.skills_by_category.len(),
+            OutputSection::Projects => output.projects.len(),
+            OutputSection::Experience => output.experience.len(),
+            OutputSection::Education => output.education.len(),
+            OutputSection::Contributions => output.contributions.len(),
+        }
+    }
+
+    /// Finds an entry by its 1-based position, or by a case-insensitive
+    /// match on its title.
+    pub fn find(&self, output: &LLMResumeOutput, query: &str) -> Option<usize> {
+        if let Ok(position) = query.trim().parse::<usize>() {
+            return (1..=self.count(output))
+                .contains(&position)
+                .then(|| position - 1);
+        }
+
+        let query = query.trim().to_lowercase();
+        (0..self.count(output))
+            .find(|&index| self.title(output, index).to_lowercase() == query)
+            .or_else(|| {
+                (0..self.count(output))
+                    .find(|&index| self.title(output, index).to_lowercase().contains(&query))
+            })
+    }
+
+    /// The entry's heading: skill category, project title, company, institution
+    /// or upstream repository.
+    pub fn title<'a>(&self, output: &'a LLMResumeOutput, index: usize) -> &'a str {
+        match self {
+            OutputSection::Skills => &output.skills_by_category[index].category,
+            OutputSection::Projects => &output.projects[index].title,
+            OutputSection::Experience => &output.experience[index].company,
+            OutputSection::Education => &output.education[index].institution,
+            OutputSection::Contributions => &output.contributions[index].project,
+        }
+    }
+
+    pub fn title_mut<'a>(&self, output: &'a mut LLMResumeOutput, index: usize) -> &'a mut String {
+        match self {
+            OutputSection::Skills => &mut output.skills_by_category[index].category,
+            OutputSection::Projects => &mut output.projects[index].title,
+            OutputSection::Experience => &mut output.experience[index].company,
+            OutputSection::Education => &mut output.education[index].institution,
+            OutputSection::Contributions => &mut output.contributions[index].project,
+        }
+    }
+
+    /// The skills of a category, or the bullet points of any other entry.
+    pub fn bullets<'a>(&self, output: &'a LLMResumeOutput, index: usize) -> &'a [String] {
+        match self {
+            OutputSection::Skills => &output.skills_by_category[index].items,
+            OutputSection::Projects => &output.projects[index].items,
+            OutputSection::Experience => &output.experience[index].accomplishments,
+            OutputSection::Education => &output.education[index].accomplishments,
+            OutputSection::Contributions => &output.contributions[index].items,
+        }
+    }
+
+    pub fn bullets_mut<'a>(
+        &self,
+        output: &'a mut LLMResumeOutput,
+        index: usize,
+    ) -> &'a mut Vec<String> {
+        match self {
+            OutputSection::Skills => &mut output.skills_by_category[index].items,
+            OutputSection::Projects => &mut output.projects[index].items,
+            OutputSection::Experience => &mut output.experience[index].accomplishments,
+            OutputSection::Education => &mut output.education[index].accomplishments,
+            OutputSection::Contributions => &mut output.contributions[index].items,
+        }
+    }
+
+    pub fn remove(&self, output: &mut LLMResumeOutput, index: usize) {
+        match self {
+            OutputSection::Skills => drop(output.skills_by_category.remove(index)),
+            OutputSection::Projects => drop(output.projects.remove(index)),
+            OutputSection::Experience => drop(output.experience.remove(index)),
+            OutputSection::Education => drop(output.education.remove(index)),
+            OutputSection::Contributions => drop(output.contributions.remove(index)),
+        }
+    }
+
+    pub fn swap(&self, output: &mut LLMResumeOutput, a: usize, b: usize) {
+        match self {
+            OutputSection::Skills => output.skills_by_category.swap(a, b),
+            OutputSection::Projects => output.projects.swap(a, b),
+            OutputSection::Experience => output.experience.swap(a, b),
+            OutputSection::Education => output.education.swap(a, b),
+            OutputSection::Contributions => output.contributions.swap(a, b),
+        }
+    }
+}
+
+/// A part of the generated content that can be regenerated on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputTarget {
+    Section(OutputSection),
+    Entry(OutputSection, usize),
+}
+
+impl OutputTarget {
+    pub fn section(&self) -> OutputSection {
+        match self {
+            OutputTarget::Section(section) | OutputTarget::Entry(section, _) => *section,
+        }
+    }
+
+    /// e.g. `the experience section` or `projects entry "syncr (Rust)"`
+    pub fn describe(&self, output: &LLMResumeOutput) -> String {
+        match self {
+            OutputTarget::Section(section) => {
+                format!("the {} section", section.label().to_lowercase())
+            }
+            OutputTarget::Entry(section, index) => format!(
+                "{} entry \"{}\"",
+                section.label().to_lowercase(),
+                section.title(output, *index)
+            ),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::sync::Mutex;
+
+use colored::Colorize;
+use eyre::{Result, eyre};
+use log::warn;
+
+use crate::utils::config::{LLMConfig, ModelPrice};
+
+/// Rough token count, at about four characters per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Tokens consumed by LLM calls.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+}
+
+impl ModelPrice {
+    /// Cost in USD
+    pub fn cost(&self, usage: TokenUsage) -> f64 {
+        (usage.input as f64 * self.input + usage.output as f64 * self.output) / 1_000_000.0
+    }
+}
+
+/// Reply size assumed for a stage's first call when the provider has no
+/// output limit, large enough for a whole generated resume.
+const DEFAULT_OUTPUT_TOKENS: u64 = 8192;
+
+#[derive(Debug, Default)]
+struct StageUsage {
+    calls: u64,
+    tokens: TokenUsage,
+    /// Largest reply of a single call
+    max_output: u64,
+    /// Some call's response had no token counts, so they were estimated
+    estimated: bool,
+}
+
+#[derive(Debug, Default)]
+struct Totals {
+    /// In the order the stages first called the LLM
+    stages: Vec<(String, StageUsage)>,
+    /// Projected cost of the calls in flight
+    reserved: f64,
+}
+
+impl Totals {
+    fn cost(&self, price: ModelPrice) -> f64 {
+        self.stages
+            .iter()
+            .map(|(_, usage)| price.cost(usage.tokens))
+            .sum()
+    }
+}
+
+/// Token usage of a run's LLM calls per stage (job cleaning, ranking,
+/// generation...), priced with the model's entry in `[config.llm.prices]`.
+pub struct UsageTracker {
+    model: String,
+    price: Option<ModelPrice>,
+    max_cost: Option<f64>,
+    totals: Mutex<Totals>,
+}
+
+impl UsageTracker {
+    pub fn new(model: &str, price: Option<ModelPrice>, max_cost: Option<f64>) -> Self {
+        UsageTracker {
+            model: model.to_string(),
+            price,
+            max_cost,
+            totals: Mutex::new(Totals::default()),
+        }
+    }
+
+    pub fn from_config(config: &LLMConfig) -> Result<Self> {
+        let model = config.model()?;
+        let price = config.prices.get(model).copied();
+        if config.max_cost.is_some() && price.is_none() {
+            return Err(eyre!(
+                "a cost limit needs the price of {} under [config.llm.prices]",
+                model
+            ));
+        }
+
+        Ok(Self::new(model, price, config.max_cost))
+    }
+
+    /// Sets aside the projected cost of a call with a prompt of
+    /// `input_tokens` (estimated) and a reply of at most `max_output_tokens`,
+    /// or else the stage's largest reply so far. Fails if that would take
+    /// the run past its cost limit, counting the calls still in flight.
+    pub fn reserve(
+        &self,
+        stage: &str,
+        input_tokens: u64,
+        max_output_tokens: Option<u64>,
+    ) -> Result<Reservation<'_>> {
+        let mut reservation = Reservation {
+            tracker: self,
+            stage: stage.to_string(),
+            cost: 0.0,
+        };
+        let (Some(max_cost), Some(price)) = (self.max_cost, self.price) else {
+            return Ok(reservation);
+        };
+
+        let mut totals = self.totals.lock().expect("usage lock poisoned");
+        let output = max_output_tokens.unwrap_or_else(|| {
+            totals
+                .stages
+                .iter()
+                .find(|(name, _)| name == stage)
+                .map_or(DEFAULT_OUTPUT_TOKENS, |(_, usage)| usage.max_output)
+        });
+        let cost = price.cost(TokenUsage {
+            input: input_tokens,
+            output,
+        });
+
+        let projected = totals.cost(price) + totals.reserved + cost;
+        if projected > max_cost {
+            return Err(eyre!(
+                "aborting {}: it could bring the estimated LLM cost to ${:.4}, over the limit of ${:.4}",
+                stage,
+                projected,
+                max_cost
+            ));
+        }
+
+        totals.reserved += cost;
+        reservation.cost = cost;
+        Ok(reservation)
+    }
+
+    /// Adds a call's tokens to its stage, `estimated` if the provider
+    /// didn't report them.
+    fn record(&self, totals: &mut Totals, stage: &str, tokens: TokenUsage, estimated: bool) {
+        let index = match totals.stages.iter().position(|(name, _)| name == stage) {
+            Some(index) => index,
+            None => {
+                totals
+                    .stages
+                    .push((stage.to_string(), StageUsage::default()));
+                totals.stages.len() - 1
+            }
+        };
+
+        let usage = &mut totals.stages[index].1;
+        usage.calls += 1;
+        usage.tokens.input += tokens.input;
+        usage.tokens.output += tokens.output;
+        usage.max_output = usage.max_output.max(tokens.output);
+        usage.estimated |= estimated;
+
+        if let Some(max_cost) = self.max_cost
+            && let Some(price) = self.price
+        {
+            let cost = totals.cost(price);
+            if cost > max_cost {
+                warn!(
+                    "estimated LLM cost ${:.4} is over the limit of ${:.4}; no further calls will be made",
+                    cost, max_cost
+                );
+            }
+        }
+    }
+
+    /// Prints input and output tokens and estimated cost per stage, unless
+    /// no LLM call was made.
+    pub fn print_summary(&self) {
+        let totals = self.totals.lock().expect("usage lock poisoned");
+        let stages = &totals.stages;
+        if stages.is_empty() {
+            return;
+        }
+
+        let mut total = StageUsage::default();
+        for (_, usage) in stages.iter() {
+            total.calls += usage.calls;
+            total.tokens.input += usage.tokens.input;
+            total.tokens.output += usage.tokens.output;
+            total.estimated |= usage.estimated;
+        }
+
+        let stage_width = stages
+            .iter()
+            .map(|(stage, _)| stage.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+
+        println!(
+            "\n{}",
+            format!("=== LLM Usage ({}) ===", self.model).cyan().bold()
+        );
+        println!(
+            "{:<stage_width$} {:>5} {:>10} {:>10} {:>10}",
+            "STAGE".bold(),
+            "CALLS".bold(),
+            "INPUT".bold(),
+            "OUTPUT".bold(),
+            "COST".bold(),
+        );
+        let row = |stage: &str, usage: &StageUsage| {
+            let approximate = if usage.estimated { "~" } else { "" };
+            println!(
+                "{:<stage_width$} {:>5} {:>10} {:>10} {:>10}",
+                stage,
+                usage.calls,
+                format!("{}{}", approximate, usage.tokens.input),
+                format!("{}{}", approximate, usage.tokens.output),
+                self.price.map_or("-".to_string(), |price| format!(
+                    "${:.4}",
+                    price.cost(usage.tokens)
+                )),
+            );
+        };
+        for (stage, usage) in stages.iter() {
+            row(stage, usage);
+        }
+        row("TOTAL", &total);
+
+        if total.estimated {
+            println!("~ partly estimated, as the provider didn't report token counts");
+        }
+        if self.price.is_none() {
+            println!(
+                "add the price of {} under [config.llm.prices] for a cost estimate",
+                self.model
+            );
+        }
+        println!();
+    }
+}
+
+/// Budget set aside for an LLM call in flight, released when dropped.
+pub struct Reservation<'a> {
+    tracker: &'a UsageTracker,
+    stage: String,
+    /// Projected cost in USD
+    cost: f64,
+}
+
+impl Reservation<'_> {
+    /// Records the tokens the call used in place of its projected cost.
+    pub fn record(self, tokens: TokenUsage, estimated: bool) {
+        let mut totals = self.tracker.totals.lock().expect("usage lock poisoned");
+        self.tracker
+            .record(&mut totals, &self.stage, tokens, estimated);
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.cost > 0.0 {
+            let mut totals = self.tracker.totals.lock().expect("usage lock poisoned");
+            totals.reserved -= self.cost;
+        }
+    }
+}
@@ -1,18 +1,65 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+use eyre::{Result, eyre};
+
+use crate::chat::agent::CoverLetter;
+use crate::latex::template::{
+    self, ContactContext, ItemContext, Layout, LetterContext, ResumeContext, SectionContext,
+    TemplateContext,
+};
+use crate::render::inline::InlineMarkup;
+use crate::render::renderer::{ResumeRenderer, ResumeSection, contacts, resume_sections};
+use crate::scraper::job::JobDescription;
 use crate::utils::config::{Config, ResumeItem};
 use crate::utils::misc::strip_url;
 
-static TEMPLATE: &str = include_str!("template.tex");
-static LOCALE_MAP_EN: [(&str, &str); 4] = [
+static LOCALE_MAP_EN: [(&str, &str); 7] = [
     ("EDUCATION_HEADER", "Education"),
     ("SKILLS_HEADER", "Technical Skills"),
     ("EXPERIENCE_HEADER", "Professional Experience"),
     ("PROJECTS_HEADER", "Key Projects"),
+    ("CONTRIBUTIONS_HEADER", "Open Source Contributions"),
+    ("PUBLICATIONS_HEADER", "Publications"),
+    ("CONTACT_HEADER", "Contact"),
 ];
-static LOCALE_MAP_PT: [(&str, &str); 4] = [
+static LOCALE_MAP_PT: [(&str, &str); 7] = [
     ("EDUCATION_HEADER", "Educação"),
     ("SKILLS_HEADER", "Habilidades Técnicas"),
     ("EXPERIENCE_HEADER", "Experiência Profissional"),
     ("PROJECTS_HEADER", "Projetos e Performance"),
+    ("CONTRIBUTIONS_HEADER", "Contribuições Open Source"),
+    ("PUBLICATIONS_HEADER", "Publicações"),
+    ("CONTACT_HEADER", "Contato"),
+];
+
+static MONTHS_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+static MONTHS_PT: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
 ];
 
 #[derive(Debug, Clone, Default)]
@@ -24,17 +71,103 @@ pub enum ResumeLanguage {
 
 impl From<&str> for ResumeLanguage {
     fn from(s: &str) -> Self {
+        ResumeLanguage::parse(s).unwrap_or_default()
+    }
+}
+
+impl ResumeLanguage {
+    /// The language of a code or name such as `pt-br` or `English`, `None`
+    /// if it isn't a supported one.
+    pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
-            "pt" | "pt-br" | "portuguese" => ResumeLanguage::Portuguese,
-            "en" | "en-us" | "english" => ResumeLanguage::English,
-            _ => ResumeLanguage::default(),
+            "pt" | "pt-br" | "portuguese" => Some(ResumeLanguage::Portuguese),
+            "en" | "en-us" | "english" => Some(ResumeLanguage::English),
+            _ => None,
         }
     }
+
+    /// English name of the language, for LLM prompts
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResumeLanguage::English => "English",
+            ResumeLanguage::Portuguese => "Portuguese",
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResumeLanguage::English => "en",
+            ResumeLanguage::Portuguese => "pt",
+        }
+    }
+
+    /// Full date as written in a letter: "October 17, 2026" / "17 de outubro de 2026"
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        let month = date.month0() as usize;
+        match self {
+            ResumeLanguage::English => {
+                format!("{} {}, {}", MONTHS_EN[month], date.day(), date.year())
+            }
+            ResumeLanguage::Portuguese => {
+                format!("{} de {} de {}", date.day(), MONTHS_PT[month], date.year())
+            }
+        }
+    }
+
+    /// Localized title of a resume section (`EDUCATION`, `SKILLS`, ...)
+    pub fn section_title(&self, key: &str) -> &'static str {
+        let locale_map = match self {
+            ResumeLanguage::English => &LOCALE_MAP_EN,
+            ResumeLanguage::Portuguese => &LOCALE_MAP_PT,
+        };
+
+        locale_map
+            .iter()
+            .find(|(header, _)| header.strip_suffix("_HEADER") == Some(key))
+            .map(|(_, title)| *title)
+            .unwrap_or_default()
+    }
+}
+
+pub struct Latex;
+
+impl InlineMarkup for Latex {
+    fn escape(text: &str) -> String {
+        let mut result = String::new();
+
+        for c in text.chars() {
+            result.push_str(&match c {
+                '&' => "\\&".to_string(),
+                '%' => "\\%".to_string(),
+                '$' => "\\$".to_string(),
+                '#' => "\\#".to_string(),
+                '_' => "\\_".to_string(),
+                '{' => "\\{".to_string(),
+                '}' => "\\}".to_string(),
+                '^' => "\\textasciicircum{}".to_string(),
+                '~' => "\\textasciitilde{}".to_string(),
+                '\\' => "\\textbackslash{}".to_string(),
+                _ => c.to_string(),
+            });
+        }
+
+        result
+    }
+
+    fn bold(inner: String) -> String {
+        format!("\\textbf{{{}}}", inner)
+    }
+
+    fn code(raw: &str) -> String {
+        format!("\\texttt{{{}}}", Self::escape(raw))
+    }
 }
 
 pub struct LatexResumeAssembler {
     language: ResumeLanguage,
     config: Config,
+    job: Option<JobDescription>,
+    layout: Layout,
 }
 
 impl LatexResumeAssembler {
@@ -42,28 +175,112 @@ impl LatexResumeAssembler {
         Self {
             language: language.into().unwrap_or_default(),
             config,
+            job: None,
+            layout: Layout::default(),
+        }
+    }
+
+    /// Exposes the job posting to the template as `job`.
+    pub fn with_job(mut self, job: &JobDescription) -> Self {
+        self.job = Some(job.clone());
+        self
+    }
+
+    /// Scales the template's font size, margins and spacing.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn assemble(&self) -> Result<String> {
+        match &self.config.latex.template {
+            Some(path) => {
+                let source = std::fs::read_to_string(path).map_err(|e| {
+                    eyre!("failed to read LaTeX template {}: {}", path.display(), e)
+                })?;
+                template::render(&path.display().to_string(), &source, &self.context())
+            }
+            None => {
+                let style = self.config.latex.style;
+                template::render(style.file_name(), style.source(), &self.context())
+            }
         }
     }
 
-    pub fn assemble(&self) -> String {
-        Self::localize(TEMPLATE.to_string(), &self.language)
-            .replace(
-                "<<NAME>>",
-                &Self::escape_latex(&self.config.resume.full_name),
-            )
-            .replace("<<CITY>>", &Self::escape_latex(&self.config.resume.city))
-            .replace(
-                "<<COUNTRY>>",
-                &Self::escape_latex(&self.config.resume.country),
-            )
-            .replace("<<HEADER>>", &self.header())
-            .replace("<<EDUCATION>>", &Self::items(&self.config.resume.education))
-            .replace("<<SKILLS>>", &Self::items(&self.config.resume.skills))
-            .replace(
-                "<<EXPERIENCE>>",
-                &Self::items(&self.config.resume.experience),
-            )
-            .replace("<<PROJECTS>>", &Self::items(&self.config.resume.projects))
+    /// Renders a cover letter with the same header as the resume.
+    pub fn assemble_cover_letter(&self, letter: &CoverLetter) -> Result<String> {
+        template::render_letter(&LetterContext {
+            resume: self.resume_context(),
+            letter: letter.into(),
+            date: self.language.format_date(chrono::Local::now().date_naive()),
+            locale: self.locale(),
+            job: self.job.as_ref().map(Into::into),
+        })
+    }
+
+    fn context(&self) -> TemplateContext {
+        let resume = &self.config.resume;
+
+        TemplateContext {
+            resume: self.resume_context(),
+            sections: resume_sections(resume, &self.language)
+                .into_iter()
+                .map(Self::section)
+                .collect(),
+            publications: Self::section(ResumeSection {
+                key: "PUBLICATIONS",
+                title: self.language.section_title("PUBLICATIONS"),
+                items: &resume.publications,
+            }),
+            locale: self.locale(),
+            job: self.job.as_ref().map(Into::into),
+            layout: self.layout,
+        }
+    }
+
+    fn resume_context(&self) -> ResumeContext {
+        let resume = &self.config.resume;
+
+        ResumeContext {
+            name: Self::escape_latex(&resume.full_name),
+            city: Self::escape_latex(&resume.city),
+            country: Self::escape_latex(&resume.country),
+            email: resume.email.clone(),
+            phone: resume.phone.clone(),
+            linkedin: resume.linkedin.clone(),
+            github: resume.github.clone(),
+            site: resume.site.clone(),
+            contacts: contacts(resume)
+                .into_iter()
+                .map(|contact| ContactContext {
+                    text: Self::escape_latex(&contact.text),
+                    link: contact.link,
+                })
+                .collect(),
+            header: self.header(),
+        }
+    }
+
+    fn locale(&self) -> BTreeMap<&'static str, &'static str> {
+        let locale_map = match self.language {
+            ResumeLanguage::English => &LOCALE_MAP_EN,
+            ResumeLanguage::Portuguese => &LOCALE_MAP_PT,
+        };
+
+        locale_map
+            .iter()
+            .copied()
+            .chain([("language", self.language.code())])
+            .collect()
+    }
+
+    fn section(section: ResumeSection) -> SectionContext {
+        SectionContext {
+            key: section.key.to_lowercase(),
+            title: section.title,
+            items: section.items.iter().map(ItemContext::from).collect(),
+            latex: Self::items(section.items),
+        }
     }
 
     fn header(&self) -> String {
@@ -165,78 +382,16 @@ impl LatexResumeAssembler {
     }
 
     fn escape_latex(text: &str) -> String {
-        let mut result = String::new();
-        let mut chars = text.chars().peekable();
-
-        while let Some(c) = chars.next() {
-            if c == '*' && chars.peek() == Some(&'*') {
-                chars.next();
-                let mut bold_text = String::new();
-                let mut found_end = false;
-
-                while let Some(bc) = chars.next() {
-                    if bc == '*' && chars.peek() == Some(&'*') {
-                        chars.next();
-                        found_end = true;
-                        break;
-                    }
-                    bold_text.push(bc);
-                }
-
-                if found_end {
-                    result.push_str(&format!("\\textbf{{{}}}", Self::escape_latex(&bold_text)));
-                } else {
-                    result.push_str("**");
-                    result.push_str(&Self::escape_latex(&bold_text));
-                }
-            } else if c == '`' {
-                let mut code_text = String::new();
-                let mut found_end = false;
-
-                for bc in chars.by_ref() {
-                    if bc == '`' {
-                        found_end = true;
-                        break;
-                    }
-                    code_text.push(bc);
-                }
-
-                if found_end {
-                    result.push_str(&format!("\\texttt{{{}}}", Self::escape_latex(&code_text)));
-                } else {
-                    result.push('`');
-                    result.push_str(&Self::escape_latex(&code_text));
-                }
-            } else {
-                result.push_str(&match c {
-                    '&' => "\\&".to_string(),
-                    '%' => "\\%".to_string(),
-                    '$' => "\\$".to_string(),
-                    '#' => "\\#".to_string(),
-                    '_' => "\\_".to_string(),
-                    '{' => "\\{".to_string(),
-                    '}' => "\\}".to_string(),
-                    '^' => "\\textasciicircum{}".to_string(),
-                    '~' => "\\textasciitilde{}".to_string(),
-                    '\\' => "\\textbackslash{}".to_string(),
-                    _ => c.to_string(),
-                });
-            }
-        }
-
-        result
+        Latex::inline(text)
     }
+}
 
-    fn localize(mut template: String, language: &ResumeLanguage) -> String {
-        let locale_map = match language {
-            ResumeLanguage::English => &LOCALE_MAP_EN,
-            ResumeLanguage::Portuguese => &LOCALE_MAP_PT,
-        };
-
-        for (key, value) in locale_map.iter() {
-            template = template.replace(&format!("<<{}>>", key), value);
-        }
+impl ResumeRenderer for LatexResumeAssembler {
+    fn render(&self) -> Result<String> {
+        self.assemble()
+    }
 
-        template
+    fn extension(&self) -> &'static str {
+        "tex"
     }
 }
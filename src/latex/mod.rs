@@ -1 +1,2 @@
 pub mod assembler;
+pub mod template;
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use eyre::{Result, eyre};
+use minijinja::syntax::SyntaxConfig;
+use minijinja::{AutoEscape, Environment};
+use serde::{Deserialize, Serialize};
+
+use crate::chat::agent::CoverLetter;
+use crate::latex::assembler::Latex;
+use crate::render::inline::InlineMarkup;
+use crate::scraper::job::JobDescription;
+use crate::utils::config::ResumeItem;
+
+static CLASSIC: &str = include_str!("template.tex");
+static COMPACT: &str = include_str!("styles/compact.tex");
+static MODERN: &str = include_str!("styles/modern.tex");
+static ACADEMIC: &str = include_str!("styles/academic.tex");
+static LETTER: &str = include_str!("letter.tex");
+
+/// Bundled resume layouts, selectable with `--style`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateStyle {
+    /// Single-column Times New Roman layout
+    #[default]
+    Classic,
+    /// Dense single-column layout that fits more on one page
+    Compact,
+    /// Two columns, with contacts, skills and education in a sidebar
+    Modern,
+    /// Academic CV with a publications section
+    Academic,
+}
+
+impl TemplateStyle {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            TemplateStyle::Classic => "template.tex",
+            TemplateStyle::Compact => "compact.tex",
+            TemplateStyle::Modern => "modern.tex",
+            TemplateStyle::Academic => "academic.tex",
+        }
+    }
+
+    pub fn source(&self) -> &'static str {
+        match self {
+            TemplateStyle::Classic => CLASSIC,
+            TemplateStyle::Compact => COMPACT,
+            TemplateStyle::Modern => MODERN,
+            TemplateStyle::Academic => ACADEMIC,
+        }
+    }
+}
+
+/// Scale factors for font size, margins and vertical spacing, applied by
+/// templates through the `scale` filter. `1.0` keeps the template's design.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Layout {
+    pub font: f64,
+    pub margin: f64,
+    pub spacing: f64,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            font: 1.0,
+            margin: 1.0,
+            spacing: 1.0,
+        }
+    }
+}
+
+impl Layout {
+    /// Progressively tighter layouts tried when a resume runs over its page limit.
+    pub const TIGHTER: [Layout; 3] = [
+        Layout {
+            font: 1.0,
+            margin: 0.85,
+            spacing: 0.6,
+        },
+        Layout {
+            font: 0.95,
+            margin: 0.7,
+            spacing: 0.4,
+        },
+        Layout {
+            font: 0.9,
+            margin: 0.6,
+            spacing: 0.25,
+        },
+    ];
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "font {:.0}%, margins {:.0}%, spacing {:.0}%",
+            self.font * 100.0,
+            self.margin * 100.0,
+            self.spacing * 100.0
+        )
+    }
+}
+
+/// Everything a LaTeX template can use. Text is already LaTeX-escaped;
+/// links, dates and contact details are passed through as written.
+#[derive(Debug, Serialize)]
+pub struct TemplateContext {
+    pub resume: ResumeContext,
+    pub sections: Vec<SectionContext>,
+    pub publications: SectionContext,
+    pub locale: BTreeMap<&'static str, &'static str>,
+    pub job: Option<JobContext>,
+    pub layout: Layout,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResumeContext {
+    pub name: String,
+    pub city: String,
+    pub country: String,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub linkedin: Option<String>,
+    pub github: Option<String>,
+    pub site: Option<String>,
+    pub contacts: Vec<ContactContext>,
+    /// Contact line of the stock template (`| email | phone | ...`)
+    pub header: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ContactContext {
+    pub text: String,
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectionContext {
+    /// `education`, `skills`, `experience`, `projects`, `contributions` or `publications`
+    pub key: String,
+    pub title: &'static str,
+    pub items: Vec<ItemContext>,
+    /// The section's items as laid out by the stock template
+    pub latex: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemContext {
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobContext {
+    pub title: String,
+    pub company: Option<String>,
+    pub description: String,
+    pub requirements: String,
+}
+
+/// Everything the cover letter template can use, with the same header data
+/// as resumes. Text is already LaTeX-escaped.
+#[derive(Debug, Serialize)]
+pub struct LetterContext {
+    pub resume: ResumeContext,
+    pub letter: CoverLetterContext,
+    /// Today's date, written out in the letter's language
+    pub date: String,
+    pub locale: BTreeMap<&'static str, &'static str>,
+    pub job: Option<JobContext>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverLetterContext {
+    pub greeting: String,
+    pub paragraphs: Vec<String>,
+    pub closing: String,
+}
+
+impl From<&ResumeItem> for ItemContext {
+    fn from(item: &ResumeItem) -> Self {
+        Self {
+            title: item.title.as_deref().map(Latex::inline),
+            date: item.date.clone(),
+            location: item.location.as_deref().map(Latex::inline),
+            description: item.description.as_deref().map(Latex::inline),
+            link: item.link.clone(),
+            items: item.items.iter().map(|i| Latex::inline(i)).collect(),
+        }
+    }
+}
+
+impl From<&CoverLetter> for CoverLetterContext {
+    fn from(letter: &CoverLetter) -> Self {
+        Self {
+            greeting: Latex::inline(&letter.greeting),
+            paragraphs: letter.paragraphs.iter().map(|p| Latex::inline(p)).collect(),
+            closing: Latex::inline(&letter.closing),
+        }
+    }
+}
+
+impl From<&JobDescription> for JobContext {
+    fn from(job: &JobDescription) -> Self {
+        Self {
+            title: Latex::escape(&job.title),
+            company: job.company.as_deref().map(Latex::escape),
+            description: Latex::escape(&job.description),
+            requirements: Latex::escape(&job.requirements),
+        }
+    }
+}
+
+/// Renders a LaTeX template written in MiniJinja with LaTeX-friendly
+/// delimiters: `\VAR{...}`, `\BLOCK{...}` and `\#{...}`.
+pub fn render(name: &str, source: &str, context: &impl Serialize) -> Result<String> {
+    let syntax = SyntaxConfig::builder()
+        .block_delimiters("\\BLOCK{", "}")
+        .variable_delimiters("\\VAR{", "}")
+        .comment_delimiters("\\#{", "}")
+        .build()
+        .map_err(|e| eyre!("invalid template syntax configuration: {}", e))?;
+
+    let mut env = Environment::new();
+    env.set_syntax(syntax);
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_filter("scale", |value: f64, factor: f64| {
+        (value * factor * 100.0).round() / 100.0
+    });
+
+    env.add_template(name, source)
+        .map_err(|e| eyre!("invalid LaTeX template {}: {}", name, e))?;
+    env.get_template(name)?
+        .render(context)
+        .map_err(|e| eyre!("failed to render LaTeX template {}: {}", name, e))
+}
+
+/// Renders the bundled cover letter template.
+pub fn render_letter(context: &LetterContext) -> Result<String> {
+    render("letter.tex", LETTER, context)
+}
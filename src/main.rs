@@ -1,26 +1,44 @@
+mod analysis;
 mod chat;
 mod latex;
 mod models;
+mod pipeline;
+mod render;
 mod scraper;
+mod tui;
 mod utils;
 
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::sync::Arc;
 
 use clap::Parser;
 use colored::Colorize;
 use eyre::Result;
 use log::{debug, error, info};
-use tectonic::latex_to_pdf;
 
-use crate::chat::agent::{ResumeAgent, resume_output_to_resume_items};
+use crate::analysis::ats;
+use crate::analysis::fingerprint::{TechFingerprint, analyze_repositories};
+use crate::chat::agent::{GenerationInput, LLMResumeOutput, RankedRepository, ResumeAgent};
 use crate::latex::assembler::LatexResumeAssembler;
-use crate::scraper::github::scrape_github_profile;
-use crate::scraper::job::get_job_description;
-use crate::utils::cli::Args;
+use crate::pipeline::artifacts::{RunDir, Stage};
+use crate::pipeline::batch::run_batch;
+use crate::pipeline::fit::PageFitter;
+use crate::pipeline::regenerate::run_regenerate;
+use crate::pipeline::resume::{
+    apply_resume_output, compile_pdf, cover_letter_path, export_json_resume, write_cover_letter,
+    write_rendered,
+};
+use crate::render::renderer::OutputFormat;
+use crate::scraper::forge::{RepoData, scrape_profile};
+use crate::scraper::github::{ExternalContribution, scrape_contributions};
+use crate::scraper::job::{JobDescription, get_job_description};
+use crate::tui::review::ReviewScreen;
+use crate::utils::cli::{Args, Command};
 use crate::utils::config::{Config, config};
 use crate::utils::log::Logger;
-use crate::utils::select_repos::select_repositories_interactive;
+use crate::utils::select_repos::{
+    RepoSelection, select_repositories_interactive, select_repositories_non_interactive,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -32,81 +50,234 @@ async fn main() -> Result<()> {
         format!("v{}", env!("CARGO_PKG_VERSION")).magenta()
     );
 
-    let config: Config = config(&args.config)?;
-
-    let github_repos = scrape_github_profile(&config).await?;
-
-    let job_description: String = get_job_description(&args).await?;
-    debug!("job description loaded: {}", job_description);
-    info!("processing job description with LLM for consistency");
-    let agent = ResumeAgent::new(
-        config
-            .llm
-            .api_key
-            .clone()
-            .ok_or_else(|| eyre::eyre!("LLM API key not configured in config.toml"))?,
-        config.llm.model.clone(),
-        config.llm.endpoint.clone(),
-        config.llm.max_retries,
-    );
-    let job_description = agent.clean_job_description(&job_description).await?;
+    let mut config: Config = config(&args.config)?;
+    if let Some(style) = args.style {
+        let latex = &mut Arc::make_mut(&mut config).latex;
+        latex.style = style;
+        // an explicit style replaces the config's template
+        latex.template = None;
+    }
+    if let Some(template) = &args.template {
+        Arc::make_mut(&mut config).latex.template = Some(template.clone());
+    }
+    if let Some(guard) = args.guard {
+        Arc::make_mut(&mut config).llm.guard = guard;
+    }
+    if let Some(max_pages) = args.max_pages {
+        Arc::make_mut(&mut config).latex.max_pages = Some(max_pages);
+    }
+    if args.deep_analysis {
+        Arc::make_mut(&mut config).analysis.deep = true;
+    }
+    if let Some(max_cost) = args.max_cost {
+        Arc::make_mut(&mut config).llm.max_cost = Some(max_cost);
+    }
+
+    match &args.command {
+        Some(Command::Batch(batch)) => return run_batch(&args, batch, config).await,
+        Some(Command::Regenerate(regenerate)) => {
+            return run_regenerate(&args, regenerate, config).await;
+        }
+        None => {}
+    }
+
+    let run = match &args.resume_from {
+        Some(dir) => RunDir::resume(dir.clone(), args.from_stage)?,
+        None => RunDir::create(args.run_dir.clone().unwrap_or_else(RunDir::default_path))?,
+    };
+    let language = run.language(args.language.as_ref())?;
+
+    let github_repos: Vec<RepoData> = run.stage(Stage::Scrape, || scrape_profile(&config)).await?;
+    let contributions: Vec<ExternalContribution> = run
+        .stage(Stage::Contributions, || scrape_contributions(&config))
+        .await?;
+
+    let agent =
+        ResumeAgent::from_config(&config.llm)?.with_private_links(config.github.private_links);
+    let job_description: JobDescription = run
+        .stage(Stage::Job, async || {
+            let job_description: String = get_job_description(&args).await?;
+            debug!("job description loaded: {}", job_description);
+            info!("processing job description with LLM for consistency");
+            agent.clean_job_description(&job_description).await
+        })
+        .await?;
     info!(
         "job description processed successfully:\nTitle: {}\nDescription: {}\nRequirements: {}",
         job_description.title, job_description.description, job_description.requirements
     );
 
-    info!("ranking repositories based on job requirements");
-    let ranked_repos = agent
-        .rank_repositories(&github_repos, &job_description)
+    let ranked_repos: Vec<RankedRepository> = run
+        .stage(Stage::Rank, async || {
+            info!("ranking repositories based on job requirements");
+            agent
+                .rank_repositories(&github_repos, &job_description)
+                .await
+        })
         .await?;
 
-    // Step 2: Interactive selection
-    let selected_repos = select_repositories_interactive(ranked_repos, &github_repos);
+    // Step 2: Interactive (or flag-driven) selection
+    let selected_repos: Vec<RepoData> = run
+        .stage(Stage::Select, async || {
+            match RepoSelection::from_args(&args)? {
+                Some(selection) => select_repositories_non_interactive(
+                    ranked_repos.clone(),
+                    &github_repos,
+                    &selection,
+                ),
+                None => Ok(select_repositories_interactive(
+                    ranked_repos.clone(),
+                    &github_repos,
+                )),
+            }
+        })
+        .await?;
     info!(
         "using {} selected repositories for resume generation",
         selected_repos.len()
     );
 
-    let llm_output = agent
-        .generate_resume_content(
-            &config.resume,
-            &job_description,
-            selected_repos,
-            &args.language,
-        )
+    let fingerprints: Vec<TechFingerprint> = run
+        .stage(Stage::Analyze, || {
+            analyze_repositories(&config, &selected_repos)
+        })
         .await?;
 
-    let (skills, experience, projects, education) = resume_output_to_resume_items(&llm_output);
+    let input = GenerationInput {
+        resume_config: &config.resume,
+        job_description: &job_description,
+        github_repos: &selected_repos,
+        contributions: &contributions,
+        fingerprints: &fingerprints,
+        language: &language,
+    };
 
-    let config = Arc::new({
-        let mut cfg = (*config).clone();
-        cfg.resume.skills = if skills.items.is_empty() {
-            cfg.resume.skills
-        } else {
-            vec![skills]
-        };
-        cfg.resume.experience = if experience.is_empty() {
-            cfg.resume.experience
-        } else {
-            experience
-        };
-        cfg.resume.projects = if projects.is_empty() {
-            cfg.resume.projects
-        } else {
-            projects
-        };
-        cfg.resume.education = if education.is_empty() {
-            cfg.resume.education
-        } else {
-            education
+    let mut llm_output: LLMResumeOutput = run
+        .stage(Stage::Generate, async || {
+            agent.generate_resume_content(&input).await
+        })
+        .await?;
+
+    if !args.non_interactive
+        && !args.no_review
+        && !run.skips(Stage::Assemble)
+        && io::stdout().is_terminal()
+    {
+        llm_output = ReviewScreen::new(&agent, input, &config, llm_output)
+            .run()
+            .await?;
+        run.save(Stage::Generate, &llm_output)?;
+    }
+
+    if let Some(path) = &args.export_json_resume {
+        export_json_resume(&config, &llm_output, path).await?;
+        info!("exported JSON Resume to {}", path.display());
+    }
+
+    if args.format != OutputFormat::Latex {
+        let resume = apply_resume_output(&config, &llm_output);
+        let path = write_rendered(args.format, resume, language.clone(), &args.output).await?;
+        report_keywords(&args, &job_description, &llm_output)?;
+        info!("generated resume at {}", path.display());
+        if args.cover_letter {
+            write_cover_letter_for(&args, &agent, &input, &config, &llm_output).await?;
+        }
+        agent.usage().print_summary();
+        return Ok(());
+    }
+
+    // PDF already compiled by the page-fitting loop, if the LaTeX was not edited afterwards
+    let mut compiled = None;
+    let latex = if run.skips(Stage::Assemble) {
+        run.load_latex()?
+    } else {
+        let mut latex = match config.latex.max_pages {
+            Some(max_pages) => {
+                let fitted = PageFitter::new(&agent, &input, max_pages)
+                    .fit(&config, llm_output.clone(), &ranked_repos)
+                    .await?;
+                llm_output = fitted.output;
+                run.save(Stage::Generate, &llm_output)?;
+                compiled = Some(fitted.pdf);
+                fitted.latex
+            }
+            None => LatexResumeAssembler::new(
+                apply_resume_output(&config, &llm_output),
+                language.clone(),
+            )
+            .with_job(&job_description)
+            .assemble()?,
         };
-        cfg
-    });
 
-    let mut latex = LatexResumeAssembler::new(config, args.language).assemble();
+        if !args.non_interactive {
+            let edited = edit_latex_interactive(latex.clone()).await?;
+            if edited != latex {
+                compiled = None;
+                latex = edited;
+            }
+        }
 
+        run.save_latex(&latex)?;
+        latex
+    };
+
+    if args.latex {
+        info!(
+            "saving intermediate LaTeX source to {}",
+            args.output.with_extension("tex").display()
+        );
+        tokio::fs::write(args.output.with_extension("tex"), latex.clone()).await?;
+    }
+
+    let pdf = match compiled {
+        Some(pdf) => pdf,
+        None => {
+            info!("compiling LaTeX to PDF");
+            compile_pdf(latex).await?
+        }
+    };
+
+    tokio::fs::write(&args.output, pdf).await?;
+    report_keywords(&args, &job_description, &llm_output)?;
+    info!("generated resume at {}", args.output.display());
+    if args.cover_letter {
+        write_cover_letter_for(&args, &agent, &input, &config, &llm_output).await?;
+    }
+    agent.usage().print_summary();
+
+    Ok(())
+}
+
+async fn write_cover_letter_for(
+    args: &Args,
+    agent: &ResumeAgent,
+    input: &GenerationInput<'_>,
+    config: &Config,
+    output: &LLMResumeOutput,
+) -> Result<()> {
+    let path = cover_letter_path(&args.output);
+    write_cover_letter(agent, input, config, output, &path, args.latex).await?;
+    info!("generated cover letter at {}", path.display());
+    Ok(())
+}
+
+/// Prints how well the resume covers the job's keywords, and saves the
+/// report as JSON when `--ats-report` is given.
+fn report_keywords(args: &Args, job: &JobDescription, output: &LLMResumeOutput) -> Result<()> {
+    let report = ats::analyze(job, output);
+    report.print();
+
+    if let Some(path) = &args.ats_report {
+        report.save(path)?;
+        info!("saved ATS keyword report to {}", path.display());
+    }
+
+    Ok(())
+}
+
+async fn edit_latex_interactive(latex: String) -> Result<String> {
     info!("would you like to edit the generated LaTeX source before compiling? (y/N): ");
-    latex = loop {
+    loop {
         io::stdout().flush().unwrap();
 
         let mut edit = String::new();
@@ -126,31 +297,10 @@ async fn main() -> Result<()> {
                     .expect("failed to open editor");
                 let edited_latex = tokio::fs::read_to_string(&temp_path).await?;
                 tokio::fs::remove_file(&temp_path).await?;
-                break edited_latex;
+                return Ok(edited_latex);
             }
-            "n" | "no" | "" => break latex,
+            "n" | "no" | "" => return Ok(latex),
             _ => error!("invalid input. please enter 'y' or 'n'."),
         }
-    };
-
-    if args.latex {
-        info!(
-            "saving intermediate LaTeX source to {}",
-            args.output.with_extension("tex").display()
-        );
-        tokio::fs::write(args.output.with_extension("tex"), latex.clone()).await?;
     }
-
-    info!("compiling LaTeX to PDF");
-    let pdf = tokio::task::spawn_blocking(|| latex_to_pdf(latex))
-        .await?
-        .map_err(|e| {
-            eprintln!("Tectonic error details: {:#?}", e);
-            eyre::eyre!("failed to compile LaTeX document: {}", e.description())
-        })?;
-
-    tokio::fs::write(&args.output, pdf).await?;
-    info!("generated resume at {}", args.output.display());
-
-    Ok(())
 }
@@ -0,0 +1,35 @@
+//! Responses of the Gitea API (https://gitea.com/api/swagger), which Forgejo
+//! and Codeberg serve as well, limited to the fields auto-resume reads.
+
+use serde::Deserialize;
+
+/// Entry of `/users/{user}/repos`
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    /// `owner/name`
+    pub full_name: String,
+    pub html_url: String,
+    pub private: bool,
+    pub fork: bool,
+    #[serde(default)]
+    pub empty: bool,
+    pub stars_count: u64,
+    pub forks_count: u64,
+    /// Size in KB
+    pub size: u64,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Missing on older servers, `null` without topics
+    #[serde(default)]
+    pub topics: Option<Vec<String>>,
+}
+
+/// Entry of `/repos/{owner}/{repo}/contents`
+#[derive(Debug, Deserialize)]
+pub struct ContentsEntry {
+    pub name: String,
+    /// `file`, `dir`, `symlink` or `submodule`
+    #[serde(rename = "type")]
+    pub kind: String,
+}
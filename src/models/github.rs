@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
@@ -47,11 +47,11 @@ pub struct RepositoryLicense {
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct Repository {
-    id: u64,
+    pub id: u64,
     node_id: String,
     pub name: String,
     full_name: String,
-    private: bool,
+    pub private: bool,
     owner: RepositoryOwner,
     html_url: String,
     description: Option<String>,
@@ -120,7 +120,7 @@ pub struct Repository {
     allow_forking: bool,
     is_template: bool,
     web_commit_signoff_required: bool,
-    topics: Vec<String>,
+    pub topics: Vec<String>,
     visibility: String,
     forks: u64,
     open_issues: u64,
@@ -135,6 +135,17 @@ pub struct RepositoryLanguages {
     pub total_byte_count: u64,
 }
 
+/// Serialized in the same `{ "Language": bytes }` shape the GitHub API returns,
+/// so persisted data round-trips through the custom `Deserialize` below.
+impl Serialize for RepositoryLanguages {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.languages.iter().map(|(lang, count)| (lang, count)))
+    }
+}
+
 impl<'de> Deserialize<'de> for RepositoryLanguages {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -152,16 +163,154 @@ impl<'de> Deserialize<'de> for RepositoryLanguages {
     }
 }
 
+/// Entry of `/repos/{owner}/{repo}/stats/contributors`
+#[derive(Debug, Deserialize)]
+pub struct ContributorStats {
+    /// `None` for commits by authors without a GitHub account
+    pub author: Option<ContributorAuthor>,
+    pub total: u64,
+    pub weeks: Vec<ContributorWeek>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContributorAuthor {
+    pub login: String,
+}
+
+/// One week of a contributor's activity: start (unix time), additions,
+/// deletions and commits.
+#[derive(Debug, Deserialize)]
+pub struct ContributorWeek {
+    pub w: i64,
+    pub a: u64,
+    pub d: u64,
+    pub c: u64,
+}
+
+/// The user's own share of a repository's history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Authorship {
+    pub commits: u64,
+    /// Commits by every contributor, the user included
+    pub total_commits: u64,
+    pub additions: u64,
+    pub deletions: u64,
+    /// Week of the first and last contributions (`YYYY-MM-DD`)
+    pub first_contribution: Option<String>,
+    pub last_contribution: Option<String>,
+}
+
+impl Authorship {
+    /// Builds the user's stats from the contributor list, matching `username`
+    /// case-insensitively. Zeroed when the user made no commits.
+    pub fn from_stats(stats: &[ContributorStats], username: &str) -> Self {
+        let total_commits = stats.iter().map(|contributor| contributor.total).sum();
+        let own = stats.iter().find(|contributor| {
+            contributor
+                .author
+                .as_ref()
+                .is_some_and(|author| author.login.eq_ignore_ascii_case(username))
+        });
+
+        let weeks: Vec<&ContributorWeek> = own
+            .iter()
+            .flat_map(|contributor| contributor.weeks.iter())
+            .filter(|week| week.c > 0)
+            .collect();
+        let week_date = |timestamp: i64| {
+            chrono::DateTime::from_timestamp(timestamp, 0).map(|date| date.date_naive().to_string())
+        };
+
+        Authorship {
+            commits: own.map(|contributor| contributor.total).unwrap_or(0),
+            total_commits,
+            additions: weeks.iter().map(|week| week.a).sum(),
+            deletions: weeks.iter().map(|week| week.d).sum(),
+            first_contribution: weeks.iter().map(|week| week.w).min().and_then(week_date),
+            last_contribution: weeks.iter().map(|week| week.w).max().and_then(week_date),
+        }
+    }
+
+    /// Percentage of the repository's commits made by the user
+    pub fn commit_share(&self) -> f64 {
+        if self.total_commits == 0 {
+            return 0.0;
+        }
+        (self.commits as f64 / self.total_commits as f64 * 1000.0).round() / 10.0
+    }
+}
+
+/// e.g. `42 of 50 commits (84%), +12000/-3000 lines, 2023-01-01 to 2024-05-05`
+impl std::fmt::Display for Authorship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} commits ({}%), +{}/-{} lines",
+            self.commits,
+            self.total_commits,
+            self.commit_share(),
+            self.additions,
+            self.deletions
+        )?;
+
+        if let (Some(first), Some(last)) = (&self.first_contribution, &self.last_contribution) {
+            write!(f, ", {} to {}", first, last)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Response of the `/search/*` endpoints
+#[derive(Debug, Deserialize)]
+pub struct SearchResults<T> {
+    pub total_count: u64,
+    pub items: Vec<T>,
+}
+
+/// Issue or pull request found by `/search/issues`
+#[derive(Debug, Deserialize)]
+pub struct IssueSearchItem {
+    pub title: String,
+    pub html_url: String,
+    /// API URL of the repository the issue belongs to
+    pub repository_url: String,
+    pub closed_at: Option<String>,
+    pub pull_request: Option<IssuePullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssuePullRequest {
+    pub merged_at: Option<String>,
+}
+
+/// The few fields of `/repos/{owner}/{repo}` needed for repositories the
+/// user does not own, which lack the fields of a full `Repository`.
+#[derive(Debug, Deserialize)]
+pub struct RepositorySummary {
+    pub full_name: String,
+    pub html_url: String,
+    pub private: bool,
+    pub stargazers_count: u64,
+    pub language: Option<String>,
+}
+
 impl Repository {
     pub fn importance_score(&self) -> u64 {
         if self.fork {
             return 0;
         }
 
-        let stars = self.stargazers_count.min(1000);
-        let forks = self.forks_count.min(100);
-        let size = self.size.min(10000) / 100;
-
-        (stars * 3) + (forks * 2) + size
+        importance_score(self.stargazers_count, self.forks_count, self.size)
     }
 }
+
+/// Ranks repositories by stars, forks and size (in KB), each capped so no
+/// single metric dominates.
+pub fn importance_score(stargazers_count: u64, forks_count: u64, size: u64) -> u64 {
+    let stars = stargazers_count.min(1000);
+    let forks = forks_count.min(100);
+    let size = size.min(10000) / 100;
+
+    (stars * 3) + (forks * 2) + size
+}
@@ -0,0 +1,50 @@
+//! Responses of the GitLab REST API (https://docs.gitlab.com/api/rest/),
+//! limited to the project fields auto-resume reads.
+
+use serde::Deserialize;
+
+/// Entry of `/users/{user}/projects`
+#[derive(Debug, Deserialize)]
+pub struct Project {
+    pub id: u64,
+    pub name: String,
+    pub web_url: String,
+    /// `public`, `internal` or `private`
+    #[serde(default)]
+    pub visibility: String,
+    pub star_count: u64,
+    pub forks_count: u64,
+    pub created_at: String,
+    pub last_activity_at: String,
+    pub default_branch: Option<String>,
+    /// Web page of the README blob, `.../-/blob/{branch}/{path}`
+    pub readme_url: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Set for forks of projects the token can see
+    pub forked_from_project: Option<serde_json::Value>,
+    #[serde(default)]
+    pub empty_repo: bool,
+    /// Only with `statistics=true` and a token with access to the project
+    pub statistics: Option<ProjectStatistics>,
+}
+
+impl Project {
+    pub fn is_private(&self) -> bool {
+        matches!(self.visibility.as_str(), "private" | "internal")
+    }
+
+    /// Path of the README in the repository, from its web page.
+    pub fn readme_path(&self) -> Option<&str> {
+        let (_, path) = self.readme_url.as_deref()?.split_once("/-/blob/")?;
+        path.strip_prefix(self.default_branch.as_deref()?)?
+            .strip_prefix('/')
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectStatistics {
+    pub commit_count: u64,
+    /// Bytes of the Git repository
+    pub repository_size: u64,
+}
@@ -0,0 +1,125 @@
+//! Responses of the GitHub GraphQL API (https://docs.github.com/graphql),
+//! limited to the repository fields auto-resume queries.
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQLResponse<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQLError {
+    pub message: String,
+}
+
+/// `viewer`, `user` or `organization`, aliased to `owner` by every query
+#[derive(Debug, Deserialize)]
+pub struct OwnerData {
+    pub owner: Option<RepositoryOwner>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryOwner {
+    pub repositories: RepositoryConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<RepositoryNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryNode {
+    pub database_id: u64,
+    pub name: String,
+    /// `owner/name`
+    pub name_with_owner: String,
+    pub is_private: bool,
+    pub stargazer_count: u64,
+    pub fork_count: u64,
+    /// Size in KB, like the REST `size`
+    pub disk_usage: Option<u64>,
+    pub created_at: String,
+    pub pushed_at: Option<String>,
+    pub languages: Option<LanguageConnection>,
+    pub repository_topics: TopicConnection,
+    /// `None` for empty repositories
+    pub default_branch_ref: Option<Ref>,
+    /// README candidates, queried under several common file names
+    pub readme_md: Option<Blob>,
+    pub readme_lower: Option<Blob>,
+    pub readme_rst: Option<Blob>,
+    pub readme_txt: Option<Blob>,
+    pub readme: Option<Blob>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageConnection {
+    /// Bytes of code in every language, not only the listed ones
+    pub total_size: u64,
+    pub edges: Vec<LanguageEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LanguageEdge {
+    pub size: u64,
+    pub node: Language,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Language {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TopicConnection {
+    pub nodes: Vec<RepositoryTopic>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepositoryTopic {
+    pub topic: Topic,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Topic {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Ref {
+    pub target: Option<Commit>,
+}
+
+/// The default branch's head. `history` is missing when the ref points at
+/// something other than a commit.
+#[derive(Debug, Deserialize)]
+pub struct Commit {
+    pub history: Option<CommitHistory>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitHistory {
+    pub total_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Blob {
+    /// `None` for binary files
+    pub text: Option<String>,
+}
@@ -0,0 +1,525 @@
+//! The JSON Resume schema (https://jsonresume.org/schema), limited to the
+//! sections auto-resume can read or produce.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::chat::agent::LLMResumeOutput;
+use crate::scraper::forge::host_label;
+use crate::utils::config::{ResumeConfig, ResumeItem};
+use crate::utils::misc::strip_url;
+
+const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// A year, optionally preceded by a numeric (`03/`) or named (`Jan`) month.
+static DATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:(\d{1,2})/|([a-zç]{3})[a-zç]*\.?\s+)?(\d{4})").expect("valid date regex")
+});
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct JsonResume {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default)]
+    pub basics: Basics,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<Work>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<Education>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<Skill>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<Project>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Meta>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Basics {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Work {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Education {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub institution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Skill {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl JsonResume {
+    /// Fills the parts of `resume` that the TOML config left empty. Explicit
+    /// TOML values always win over the imported ones.
+    pub fn apply_to(&self, resume: &mut ResumeConfig) {
+        let basics = &self.basics;
+        let location = basics.location.clone().unwrap_or_default();
+
+        fill(&mut resume.full_name, basics.name.as_ref());
+        fill(&mut resume.city, location.city.as_ref());
+        fill(
+            &mut resume.country,
+            location.country_code.as_ref().or(location.region.as_ref()),
+        );
+        resume.email = resume.email.take().or_else(|| basics.email.clone());
+        resume.phone = resume.phone.take().or_else(|| basics.phone.clone());
+        resume.site = resume.site.take().or_else(|| basics.url.clone());
+        resume.linkedin = resume
+            .linkedin
+            .take()
+            .or_else(|| self.profile_url("linkedin"));
+        resume.github = resume.github.take().or_else(|| self.profile_url("github"));
+
+        if resume.experience.is_empty() {
+            resume.experience = self.work.iter().map(Work::to_resume_item).collect();
+        }
+        if resume.education.is_empty() {
+            resume.education = self
+                .education
+                .iter()
+                .map(Education::to_resume_item)
+                .collect();
+        }
+        if resume.projects.is_empty() {
+            resume.projects = self.projects.iter().map(Project::to_resume_item).collect();
+        }
+        if resume.skills.is_empty() && !self.skills.is_empty() {
+            resume.skills = vec![ResumeItem {
+                title: None,
+                date: None,
+                location: None,
+                description: None,
+                link: None,
+                items: self.skills.iter().map(Skill::to_line).collect(),
+            }];
+        }
+
+        // give the LLM the imported background to tailor, unless the user wrote their own
+        if resume.experience_context.is_none() && !self.work.is_empty() {
+            resume.experience_context = Some(context(&resume.experience));
+        }
+        if resume.education_context.is_none() && !self.education.is_empty() {
+            resume.education_context = Some(context(&resume.education));
+        }
+        if resume.skills_context.is_none() && !self.skills.is_empty() {
+            resume.skills_context = Some(
+                [basics.summary.clone()]
+                    .into_iter()
+                    .flatten()
+                    .chain(self.skills.iter().map(Skill::to_line))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+        }
+    }
+
+    fn profile_url(&self, network: &str) -> Option<String> {
+        self.basics
+            .profiles
+            .iter()
+            .find(|p| {
+                p.network
+                    .as_deref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(network))
+            })
+            .and_then(|p| p.url.clone())
+    }
+
+    /// Builds a JSON Resume document from the tailored LLM output, taking the
+    /// personal details from the resume config.
+    pub fn from_output(resume: &ResumeConfig, output: &LLMResumeOutput) -> Self {
+        let mut profiles = Vec::new();
+        for (network, url) in [("LinkedIn", &resume.linkedin), ("GitHub", &resume.github)] {
+            if let Some(url) = url {
+                profiles.push(Profile {
+                    network: Some(network.to_string()),
+                    username: strip_url(url)
+                        .trim_end_matches('/')
+                        .rsplit('/')
+                        .next()
+                        .map(|s| s.to_string()),
+                    url: Some(url.clone()),
+                });
+            }
+        }
+
+        JsonResume {
+            schema: Some(SCHEMA_URL.to_string()),
+            basics: Basics {
+                name: Some(resume.full_name.clone()),
+                label: None,
+                email: resume.email.clone(),
+                phone: resume.phone.clone(),
+                url: resume.site.clone(),
+                summary: None,
+                location: Some(country_location(&resume.city, &resume.country)),
+                profiles,
+            },
+            work: output
+                .experience
+                .iter()
+                .map(|exp| {
+                    let (start_date, end_date) = parse_date_range(&exp.date);
+                    Work {
+                        name: Some(exp.company.clone()),
+                        location: Some(exp.location.clone()),
+                        position: Some(exp.position.clone()),
+                        url: None,
+                        start_date,
+                        end_date,
+                        summary: None,
+                        highlights: exp.accomplishments.clone(),
+                    }
+                })
+                .collect(),
+            education: output
+                .education
+                .iter()
+                .map(|edu| {
+                    let (start_date, end_date) = parse_date_range(&edu.date);
+                    Education {
+                        institution: Some(edu.institution.clone()),
+                        study_type: Some(edu.degree.clone()),
+                        start_date,
+                        end_date,
+                        courses: edu.accomplishments.clone(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            skills: output
+                .skills_by_category
+                .iter()
+                .map(|cat| Skill {
+                    name: Some(cat.category.clone()),
+                    level: None,
+                    keywords: cat.items.clone(),
+                })
+                .collect(),
+            projects: output
+                .projects
+                .iter()
+                .map(|proj| {
+                    // "Project Name (Rust / LLM)" -> name + keywords
+                    let (name, keywords) = match proj.title.split_once('(') {
+                        Some((name, rest)) => (
+                            name.trim().to_string(),
+                            rest.trim_end_matches(')')
+                                .split(['/', ','])
+                                .map(|k| k.trim().to_string())
+                                .filter(|k| !k.is_empty())
+                                .collect(),
+                        ),
+                        None => (proj.title.clone(), Vec::new()),
+                    };
+                    Project {
+                        name: Some(name),
+                        highlights: proj.items.clone(),
+                        keywords,
+                        url: (!proj.hide_link).then(|| proj.link.clone()),
+                        ..Default::default()
+                    }
+                })
+                .chain(output.contributions.iter().map(|contribution| Project {
+                    name: Some(contribution.project.clone()),
+                    description: Some("Open source contribution".to_string()),
+                    highlights: contribution.items.clone(),
+                    url: Some(contribution.link.clone()),
+                    ..Default::default()
+                }))
+                .collect(),
+            meta: Some(Meta {
+                version: Some("v1.0.0".to_string()),
+                last_modified: Some(chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()),
+            }),
+        }
+    }
+}
+
+impl Work {
+    fn to_resume_item(&self) -> ResumeItem {
+        ResumeItem {
+            title: self.name.clone(),
+            date: date_range(self.start_date.as_deref(), self.end_date.as_deref()),
+            location: self.location.clone(),
+            description: self.position.clone(),
+            link: None,
+            items: if self.highlights.is_empty() {
+                self.summary.iter().cloned().collect()
+            } else {
+                self.highlights.clone()
+            },
+        }
+    }
+}
+
+impl Education {
+    fn to_resume_item(&self) -> ResumeItem {
+        let degree = match (&self.study_type, &self.area) {
+            (Some(study_type), Some(area)) => Some(format!("{}, {}", study_type, area)),
+            (study_type, area) => study_type.clone().or_else(|| area.clone()),
+        };
+
+        ResumeItem {
+            title: self.institution.clone(),
+            date: date_range(self.start_date.as_deref(), self.end_date.as_deref()),
+            location: None,
+            description: degree,
+            link: None,
+            items: self
+                .score
+                .iter()
+                .map(|score| format!("GPA: {}", score))
+                .chain(self.courses.iter().cloned())
+                .collect(),
+        }
+    }
+}
+
+impl Project {
+    fn to_resume_item(&self) -> ResumeItem {
+        let title = match (&self.name, self.keywords.is_empty()) {
+            (Some(name), false) => Some(format!("{} ({})", name, self.keywords.join(", "))),
+            (name, _) => name.clone(),
+        };
+
+        ResumeItem {
+            title,
+            date: None,
+            location: self.url.as_deref().map(host_label),
+            description: None,
+            link: self.url.clone(),
+            items: if self.highlights.is_empty() {
+                self.description.iter().cloned().collect()
+            } else {
+                self.highlights.clone()
+            },
+        }
+    }
+}
+
+impl Skill {
+    fn to_line(&self) -> String {
+        match &self.name {
+            Some(name) => format!("**{}**: {}", name, self.keywords.join(", ")),
+            None => self.keywords.join(", "),
+        }
+    }
+}
+
+fn fill(field: &mut String, value: Option<&String>) {
+    if field.trim().is_empty()
+        && let Some(value) = value
+    {
+        *field = value.clone();
+    }
+}
+
+/// Flattens resume items into a plain-text summary for LLM context.
+fn context(items: &[ResumeItem]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let heading = [&item.description, &item.title, &item.location, &item.date]
+                .into_iter()
+                .flatten()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" | ");
+            let bullets = item
+                .items
+                .iter()
+                .map(|b| format!("  - {}", b))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("{}\n{}", heading, bullets)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// "2020-03-01" / "2020-03" / "2020" to "03/2020" / "2020".
+fn display_date(date: &str) -> String {
+    let mut parts = date.split('-');
+    match (parts.next(), parts.next()) {
+        (Some(year), Some(month)) => format!("{}/{}", month, year),
+        _ => date.to_string(),
+    }
+}
+
+fn date_range(start: Option<&str>, end: Option<&str>) -> Option<String> {
+    match (start, end) {
+        (Some(start), Some(end)) => {
+            Some(format!("{} - {}", display_date(start), display_date(end)))
+        }
+        (Some(start), None) => Some(format!("{} - Present", display_date(start))),
+        (None, Some(end)) => Some(display_date(end)),
+        (None, None) => None,
+    }
+}
+
+/// `countryCode` only holds ISO-3166 codes ("BR"), so a country name
+/// ("Brazil") goes into `region`.
+fn country_location(city: &str, country: &str) -> Location {
+    let is_code = country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic());
+    Location {
+        city: Some(city.to_string()),
+        country_code: is_code.then(|| country.to_ascii_uppercase()),
+        region: (!is_code && !country.is_empty()).then(|| country.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Best-effort extraction of ISO 8601 dates from free-form ranges such as
+/// "Jan 2022 - Present", "03/2019 – 12/2021" or "2018 - 2022".
+fn parse_date_range(text: &str) -> (Option<String>, Option<String>) {
+    let mut dates = DATE.captures_iter(text).map(|c| {
+        let month = c
+            .get(1)
+            .and_then(|m| m.as_str().parse::<u32>().ok())
+            .or_else(|| c.get(2).and_then(|m| month_number(m.as_str())));
+        match month {
+            Some(month) if (1..=12).contains(&month) => format!("{}-{:02}", &c[3], month),
+            _ => c[3].to_string(),
+        }
+    });
+
+    (dates.next(), dates.next())
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let months = [
+        ["jan", "jan"],
+        ["feb", "fev"],
+        ["mar", "mar"],
+        ["apr", "abr"],
+        ["may", "mai"],
+        ["jun", "jun"],
+        ["jul", "jul"],
+        ["aug", "ago"],
+        ["sep", "set"],
+        ["oct", "out"],
+        ["nov", "nov"],
+        ["dec", "dez"],
+    ];
+    let name = name.to_lowercase();
+
+    months
+        .iter()
+        .position(|names| names.contains(&name.as_str()))
+        .map(|i| i as u32 + 1)
+}
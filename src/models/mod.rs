@@ -1 +1,5 @@
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
+pub mod graphql;
+pub mod json_resume;
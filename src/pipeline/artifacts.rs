@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+use std::{fmt, fs};
+
+use clap::ValueEnum;
+use eyre::{Result, eyre};
+use log::{debug, info};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::latex::assembler::ResumeLanguage;
+
+const RUNS_DIR: &str = "runs";
+
+/// Code of the run's resume language (`en`, `pt`)
+const LANGUAGE_FILE: &str = "language";
+
+/// Pipeline stages, in execution order. Each one persists its output to the
+/// run directory so a later run can pick up from any point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Stage {
+    /// GitHub profile scrape (`Vec<RepoData>`)
+    Scrape,
+    /// Merged pull requests to other projects (`Vec<ExternalContribution>`)
+    Contributions,
+    /// Cleaned job description (`JobDescription`)
+    Job,
+    /// LLM repository ranking (`Vec<RankedRepository>`)
+    Rank,
+    /// Repositories picked for the resume (`Vec<RepoData>`)
+    Select,
+    /// Code analysis of the picked repositories (`Vec<TechFingerprint>`),
+    /// empty unless deep analysis is enabled
+    Analyze,
+    /// Generated resume content (`LLMResumeOutput`)
+    Generate,
+    /// Assembled (and possibly hand-edited) LaTeX source
+    Assemble,
+    /// PDF compilation, which has no artifact of its own
+    Compile,
+}
+
+impl Stage {
+    const ALL: [Stage; 9] = [
+        Stage::Scrape,
+        Stage::Contributions,
+        Stage::Job,
+        Stage::Rank,
+        Stage::Select,
+        Stage::Analyze,
+        Stage::Generate,
+        Stage::Assemble,
+        Stage::Compile,
+    ];
+
+    fn artifact(&self) -> Option<&'static str> {
+        match self {
+            Stage::Scrape => Some("github_repos.json"),
+            Stage::Contributions => Some("contributions.json"),
+            Stage::Job => Some("job_description.json"),
+            Stage::Rank => Some("ranked_repositories.json"),
+            Stage::Select => Some("selected_repositories.json"),
+            Stage::Analyze => Some("fingerprints.json"),
+            Stage::Generate => Some("resume_content.json"),
+            Stage::Assemble => Some("resume.tex"),
+            Stage::Compile => None,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(value) => f.write_str(value.get_name()),
+            None => write!(f, "{:?}", self),
+        }
+    }
+}
+
+/// A directory holding the artifacts of one pipeline run.
+pub struct RunDir {
+    path: PathBuf,
+    from: Stage,
+}
+
+impl RunDir {
+    /// Default location for a fresh run: `runs/<timestamp>`
+    pub fn default_path() -> PathBuf {
+        Path::new(RUNS_DIR).join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string())
+    }
+
+    /// Starts a fresh run that executes every stage.
+    pub fn create(path: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&path)?;
+        info!("saving run artifacts to {}", path.display());
+
+        Ok(Self {
+            path,
+            from: Stage::Scrape,
+        })
+    }
+
+    /// Reopens an earlier run. Stages before `from` are loaded from disk; when
+    /// `from` is not given, the first stage without an artifact is used.
+    pub fn resume(path: PathBuf, from: Option<Stage>) -> Result<Self> {
+        if !path.is_dir() {
+            return Err(eyre!("run directory not found: {}", path.display()));
+        }
+
+        let from = from.unwrap_or_else(|| {
+            Stage::ALL
+                .into_iter()
+                .find(|stage| {
+                    stage
+                        .artifact()
+                        .is_none_or(|artifact| !path.join(artifact).exists())
+                })
+                .unwrap_or(Stage::Compile)
+        });
+
+        info!("resuming run {} from stage '{}'", path.display(), from);
+
+        Ok(Self { path, from })
+    }
+
+    /// The run's resume language: `language` when given on the command line,
+    /// else the one saved by an earlier session of the run, else Portuguese.
+    /// Saved so resuming or regenerating keeps writing in it.
+    pub fn language(&self, language: Option<&ResumeLanguage>) -> Result<ResumeLanguage> {
+        let path = self.path.join(LANGUAGE_FILE);
+        let language = match language {
+            Some(language) => language.clone(),
+            None => match fs::read_to_string(&path) {
+                Ok(code) => ResumeLanguage::from(code.trim()),
+                Err(_) => ResumeLanguage::Portuguese,
+            },
+        };
+
+        fs::write(&path, language.code())?;
+        debug!("run language: {}", language.name());
+        Ok(language)
+    }
+
+    /// Whether `stage` is reused from disk instead of being executed.
+    pub fn skips(&self, stage: Stage) -> bool {
+        stage < self.from
+    }
+
+    fn artifact_path(&self, stage: Stage) -> PathBuf {
+        self.path
+            .join(stage.artifact().expect("stage has no artifact"))
+    }
+
+    pub fn load<T: DeserializeOwned>(&self, stage: Stage) -> Result<T> {
+        let path = self.artifact_path(stage);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            eyre!(
+                "failed to load '{}' artifact {} (resume from an earlier stage?): {}",
+                stage,
+                path.display(),
+                e
+            )
+        })?;
+
+        info!("loaded '{}' stage from {}", stage, path.display());
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save<T: Serialize>(&self, stage: Stage, value: &T) -> Result<()> {
+        let path = self.artifact_path(stage);
+        fs::write(&path, serde_json::to_string_pretty(value)?)?;
+
+        debug!("saved '{}' stage to {}", stage, path.display());
+        Ok(())
+    }
+
+    /// Loads the artifact of `stage` when it is skipped, otherwise runs
+    /// `compute` and persists its result.
+    pub async fn stage<T>(
+        &self,
+        stage: Stage,
+        compute: impl AsyncFnOnce() -> Result<T>,
+    ) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        if self.skips(stage) {
+            return self.load(stage);
+        }
+
+        let value = compute().await?;
+        self.save(stage, &value)?;
+        Ok(value)
+    }
+
+    pub fn load_latex(&self) -> Result<String> {
+        let path = self.artifact_path(Stage::Assemble);
+        let latex = fs::read_to_string(&path)
+            .map_err(|e| eyre!("failed to load LaTeX source {}: {}", path.display(), e))?;
+
+        info!("loaded '{}' stage from {}", Stage::Assemble, path.display());
+        Ok(latex)
+    }
+
+    pub fn save_latex(&self, latex: &str) -> Result<()> {
+        let path = self.artifact_path(Stage::Assemble);
+        fs::write(&path, latex)?;
+
+        debug!("saved '{}' stage to {}", Stage::Assemble, path.display());
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use colored::Colorize;
+use eyre::{Result, eyre};
+use log::{error, info};
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use crate::analysis::fingerprint::analyze_repositories;
+use crate::chat::agent::{GenerationInput, ResumeAgent};
+use crate::latex::assembler::{LatexResumeAssembler, ResumeLanguage};
+use crate::pipeline::fit::PageFitter;
+use crate::pipeline::resume::{apply_resume_output, compile_pdf, write_rendered};
+use crate::render::renderer::OutputFormat;
+use crate::scraper::forge::{RepoData, scrape_profile};
+use crate::scraper::github::{ExternalContribution, scrape_contributions};
+use crate::scraper::job::JobScraper;
+use crate::utils::cli::{Args, BatchArgs};
+use crate::utils::config::Config;
+use crate::utils::select_repos::{RepoSelection, select_repositories_non_interactive};
+
+/// A single job posting to generate a resume for.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchJob {
+    pub url: Option<String>,
+    pub file: Option<PathBuf>,
+    pub language: Option<String>,
+    pub output: PathBuf,
+    #[serde(default)]
+    pub repos: Vec<String>,
+    pub top: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TomlManifest {
+    #[serde(rename = "job", default)]
+    jobs: Vec<BatchJob>,
+}
+
+impl BatchJob {
+    fn label(&self) -> String {
+        match (&self.url, &self.file) {
+            (Some(url), _) => url.clone(),
+            (None, Some(file)) => file.display().to_string(),
+            (None, None) => "<missing source>".to_string(),
+        }
+    }
+
+    fn selection(&self, default_top: usize) -> RepoSelection {
+        if self.repos.is_empty() {
+            RepoSelection::Top(self.top.unwrap_or(default_top))
+        } else {
+            RepoSelection::Names(self.repos.clone())
+        }
+    }
+
+    /// Resolves relative `file` and `output` paths against the manifest directory.
+    fn resolve_paths(mut self, base: &Path) -> Self {
+        if let Some(file) = &self.file
+            && file.is_relative()
+        {
+            self.file = Some(base.join(file));
+        }
+        if self.output.is_relative() {
+            self.output = base.join(&self.output);
+        }
+        self
+    }
+}
+
+/// Reads a batch manifest, either TOML (`[[job]]` tables) or JSON Lines
+/// (one job object per line, selected by a `.jsonl`/`.ndjson` extension).
+pub fn read_manifest(path: &Path) -> Result<Vec<BatchJob>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("failed to read manifest {}: {}", path.display(), e))?;
+
+    let jobs: Vec<BatchJob> = match path.extension().and_then(|e| e.to_str()) {
+        Some("jsonl" | "ndjson") => content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| eyre!("invalid job on manifest line {}: {}", i + 1, e))
+            })
+            .collect::<Result<_>>()?,
+        _ => toml::from_str::<TomlManifest>(&content)?.jobs,
+    };
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    let jobs: Vec<BatchJob> = jobs.into_iter().map(|j| j.resolve_paths(base)).collect();
+
+    if let Some(job) = jobs.iter().find(|j| j.url.is_none() && j.file.is_none()) {
+        return Err(eyre!(
+            "manifest job with output {} has neither `url` nor `file`",
+            job.output.display()
+        ));
+    }
+    if let Some((job, language)) = jobs.iter().find_map(|j| {
+        j.language
+            .as_deref()
+            .filter(|language| ResumeLanguage::parse(language).is_none())
+            .map(|language| (j, language))
+    }) {
+        return Err(eyre!(
+            "manifest job with output {} has unknown language {:?} (expected en or pt)",
+            job.output.display(),
+            language
+        ));
+    }
+
+    Ok(jobs)
+}
+
+pub async fn run_batch(args: &Args, batch: &BatchArgs, config: Config) -> Result<()> {
+    let jobs = read_manifest(&batch.manifest)?;
+    if jobs.is_empty() {
+        return Err(eyre!("manifest {} has no jobs", batch.manifest.display()));
+    }
+
+    info!(
+        "running batch of {} jobs (concurrency: {})",
+        jobs.len(),
+        batch.concurrency
+    );
+
+    // scraped once and shared by every job
+    let github_repos = Arc::new(scrape_profile(&config).await?);
+    let contributions = Arc::new(scrape_contributions(&config).await?);
+    let agent = Arc::new(
+        ResumeAgent::from_config(&config.llm)?.with_private_links(config.github.private_links),
+    );
+    let semaphore = Arc::new(Semaphore::new(batch.concurrency.max(1)));
+
+    let mut tasks = Vec::new();
+    for job in jobs.into_iter() {
+        let config = config.clone();
+        let github_repos = Arc::clone(&github_repos);
+        let contributions = Arc::clone(&contributions);
+        let agent = Arc::clone(&agent);
+        let semaphore = Arc::clone(&semaphore);
+        let settings = JobSettings {
+            language: job
+                .language
+                .as_deref()
+                .and_then(ResumeLanguage::parse)
+                .or_else(|| args.language.clone())
+                .unwrap_or(ResumeLanguage::Portuguese),
+            selection: job.selection(args.top),
+            format: args.format,
+            save_latex: args.latex,
+        };
+
+        tasks.push((
+            job.clone(),
+            tokio::spawn(async move {
+                let result = async {
+                    let _permit = semaphore.acquire_owned().await?;
+                    info!("starting job: {}", job.label());
+
+                    run_job(
+                        &job,
+                        config,
+                        &agent,
+                        &github_repos,
+                        &contributions,
+                        settings,
+                    )
+                    .await
+                }
+                .await;
+
+                match &result {
+                    Ok(path) => info!("finished job: {} -> {}", job.label(), path.display()),
+                    Err(e) => error!("job failed: {}: {}", job.label(), e),
+                }
+
+                (job, result)
+            }),
+        ));
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (job, task) in tasks {
+        // a panicking job fails on its own, leaving the others' results
+        let result = task.await.unwrap_or_else(|e| {
+            error!("job failed: {}: {}", job.label(), e);
+            (job, Err(eyre!("job task failed: {}", e)))
+        });
+        results.push(result);
+    }
+
+    print_summary(&results);
+    agent.usage().print_summary();
+
+    let failures = results.iter().filter(|(_, r)| r.is_err()).count();
+    if failures > 0 {
+        return Err(eyre!("{} of {} batch jobs failed", failures, results.len()));
+    }
+
+    Ok(())
+}
+
+/// Per-job options resolved from the manifest entry and the command line.
+struct JobSettings {
+    language: ResumeLanguage,
+    selection: RepoSelection,
+    format: OutputFormat,
+    save_latex: bool,
+}
+
+async fn run_job(
+    job: &BatchJob,
+    config: Config,
+    agent: &ResumeAgent,
+    github_repos: &[RepoData],
+    contributions: &[ExternalContribution],
+    settings: JobSettings,
+) -> Result<PathBuf> {
+    let raw_description = match (&job.url, &job.file) {
+        (Some(url), _) => JobScraper::from_url(url).await?,
+        (None, Some(file)) => JobScraper::from_file(file).await?,
+        (None, None) => unreachable!("validated when reading the manifest"),
+    };
+
+    let job_description = agent.clean_job_description(&raw_description).await?;
+    let ranked_repos = agent
+        .rank_repositories(github_repos, &job_description)
+        .await?;
+    let selected_repos = select_repositories_non_interactive(
+        ranked_repos.clone(),
+        github_repos,
+        &settings.selection,
+    )?;
+
+    let fingerprints = analyze_repositories(&config, &selected_repos).await?;
+
+    let input = GenerationInput {
+        resume_config: &config.resume,
+        job_description: &job_description,
+        github_repos: &selected_repos,
+        contributions,
+        fingerprints: &fingerprints,
+        language: &settings.language,
+    };
+    let llm_output = agent.generate_resume_content(&input).await?;
+
+    if let Some(parent) = job.output.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    if settings.format != OutputFormat::Latex {
+        let config = apply_resume_output(&config, &llm_output);
+        return write_rendered(
+            settings.format,
+            config,
+            settings.language.clone(),
+            &job.output,
+        )
+        .await;
+    }
+
+    let (latex, pdf) = match config.latex.max_pages {
+        Some(max_pages) => {
+            let fitted = PageFitter::new(agent, &input, max_pages)
+                .fit(&config, llm_output, &ranked_repos)
+                .await?;
+            (fitted.latex, fitted.pdf)
+        }
+        None => {
+            let latex = LatexResumeAssembler::new(
+                apply_resume_output(&config, &llm_output),
+                settings.language.clone(),
+            )
+            .with_job(&job_description)
+            .assemble()?;
+            let pdf = compile_pdf(latex.clone()).await?;
+            (latex, pdf)
+        }
+    };
+
+    if settings.save_latex {
+        tokio::fs::write(job.output.with_extension("tex"), &latex).await?;
+    }
+
+    tokio::fs::write(&job.output, pdf).await?;
+
+    Ok(job.output.clone())
+}
+
+fn print_summary(results: &[(BatchJob, Result<PathBuf>)]) {
+    let label_width = results
+        .iter()
+        .map(|(job, _)| job.label().chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(3, 60);
+
+    println!("\n{}", "=== Batch Summary ===".cyan().bold());
+    println!(
+        "{:<6} {:<label_width$} {}",
+        "STATUS".bold(),
+        "JOB".bold(),
+        "OUTPUT / ERROR".bold(),
+    );
+
+    for (job, result) in results {
+        let label: String = job.label().chars().take(label_width).collect();
+        match result {
+            Ok(path) => println!(
+                "{:<6} {:<label_width$} {}",
+                "OK".green().bold(),
+                label,
+                path.display()
+            ),
+            Err(e) => println!(
+                "{:<6} {:<label_width$} {}",
+                "FAIL".red().bold(),
+                label,
+                e.to_string().red()
+            ),
+        }
+    }
+
+    let succeeded = results.iter().filter(|(_, r)| r.is_ok()).count();
+    println!(
+        "\n{} succeeded, {} failed\n",
+        succeeded.to_string().green(),
+        (results.len() - succeeded).to_string().red()
+    );
+}
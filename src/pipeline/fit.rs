@@ -0,0 +1,179 @@
+use eyre::{Result, eyre};
+use log::{info, warn};
+
+use crate::analysis::guard::repo_key;
+use crate::chat::agent::{
+    GenerationInput, LLMResumeOutput, ProjectEntry, RankedRepository, ResumeAgent,
+};
+use crate::latex::assembler::LatexResumeAssembler;
+use crate::latex::template::Layout;
+use crate::pipeline::resume::{apply_resume_output, compile_pdf};
+use crate::utils::config::Config;
+
+/// Rounds of LLM shortening / project dropping tried after the tightest layout.
+const MAX_CONTENT_ROUNDS: usize = 4;
+
+pub struct FittedResume {
+    /// The content that was compiled, possibly shortened
+    pub output: LLMResumeOutput,
+    pub latex: String,
+    pub pdf: Vec<u8>,
+    pub pages: usize,
+}
+
+pub fn count_pages(pdf: &[u8]) -> Result<usize> {
+    let document = lopdf::Document::load_mem(pdf)
+        .map_err(|e| eyre!("failed to read the compiled PDF: {}", e))?;
+    Ok(document.get_pages().len())
+}
+
+/// Compiles the resume and, while it runs over `max_pages`, first tightens
+/// the layout and then shortens the content until it fits.
+pub struct PageFitter<'a> {
+    agent: &'a ResumeAgent,
+    /// What the content was generated from, which shortened content is
+    /// checked against
+    input: &'a GenerationInput<'a>,
+    max_pages: usize,
+}
+
+impl<'a> PageFitter<'a> {
+    pub fn new(agent: &'a ResumeAgent, input: &'a GenerationInput<'a>, max_pages: usize) -> Self {
+        Self {
+            agent,
+            input,
+            max_pages,
+        }
+    }
+
+    /// `config` is the configured resume, before the LLM output is merged in.
+    pub async fn fit(
+        &self,
+        config: &Config,
+        mut output: LLMResumeOutput,
+        ranked_repos: &[RankedRepository],
+    ) -> Result<FittedResume> {
+        let mut resume = self.compile(config, &output, Layout::default()).await?;
+        info!(
+            "compiled resume has {} page(s) (max: {})",
+            resume.pages, self.max_pages
+        );
+
+        for (step, layout) in Layout::TIGHTER.into_iter().enumerate() {
+            if resume.pages <= self.max_pages {
+                return Ok(resume);
+            }
+
+            info!(
+                "tightening layout, step {}/{} ({})",
+                step + 1,
+                Layout::TIGHTER.len(),
+                layout
+            );
+            resume = self.compile(config, &output, layout).await?;
+            info!("resume now has {} page(s)", resume.pages);
+        }
+
+        let layout = Layout::TIGHTER[Layout::TIGHTER.len() - 1];
+        // an LLM round can come back longer than an earlier one
+        let mut shortest: Option<FittedResume> = None;
+        for round in 0..MAX_CONTENT_ROUNDS {
+            if resume.pages <= self.max_pages {
+                return Ok(resume);
+            }
+
+            if round % 2 == 1
+                && let Some(project) = drop_lowest_ranked_project(&mut output, ranked_repos)
+            {
+                info!("dropping the lowest-ranked project: {}", project.title);
+            } else {
+                output = self
+                    .agent
+                    .shorten_resume_content(self.input, &output, resume.pages, self.max_pages)
+                    .await?;
+            }
+
+            let next = self.compile(config, &output, layout).await?;
+            info!("resume now has {} page(s)", next.pages);
+            let previous = std::mem::replace(&mut resume, next);
+            if shortest
+                .as_ref()
+                .is_none_or(|shortest| previous.pages < shortest.pages)
+            {
+                shortest = Some(previous);
+            }
+        }
+
+        if resume.pages > self.max_pages {
+            // the earliest of equally long versions keeps the most content
+            if let Some(shortest) = shortest
+                && shortest.pages <= resume.pages
+            {
+                resume = shortest;
+            }
+            warn!(
+                "resume still has {} pages after fitting (max: {}), keeping the shortest version",
+                resume.pages, self.max_pages
+            );
+        }
+
+        Ok(resume)
+    }
+
+    async fn compile(
+        &self,
+        config: &Config,
+        output: &LLMResumeOutput,
+        layout: Layout,
+    ) -> Result<FittedResume> {
+        let latex = LatexResumeAssembler::new(
+            apply_resume_output(config, output),
+            self.input.language.clone(),
+        )
+        .with_job(self.input.job_description)
+        .with_layout(layout)
+        .assemble()?;
+
+        let pdf = compile_pdf(latex.clone()).await?;
+        let pages = count_pages(&pdf)?;
+
+        Ok(FittedResume {
+            output: output.clone(),
+            latex,
+            pdf,
+            pages,
+        })
+    }
+}
+
+/// Removes the project whose repository ranked lowest. Projects that match
+/// no ranked repository (e.g. added by hand) go first.
+fn drop_lowest_ranked_project(
+    output: &mut LLMResumeOutput,
+    ranked_repos: &[RankedRepository],
+) -> Option<ProjectEntry> {
+    let rank = |project: &ProjectEntry| {
+        let link = repo_key(&project.link);
+        ranked_repos
+            .iter()
+            .find(|ranked| {
+                // ranked by `owner/name`, or a bare name in older runs
+                let key = repo_key(&ranked.name);
+                let name = key.rsplit('/').next().unwrap_or(&key);
+                link == key
+                    || link.ends_with(&format!("/{}", key))
+                    || project.title.to_lowercase().contains(name)
+            })
+            .map(|ranked| ranked.rank)
+            .unwrap_or(usize::MAX)
+    };
+
+    let index = output
+        .projects
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, project)| (rank(project), *i))
+        .map(|(i, _)| i)?;
+
+    Some(output.projects.remove(index))
+}
@@ -0,0 +1,5 @@
+pub mod artifacts;
+pub mod batch;
+pub mod fit;
+pub mod regenerate;
+pub mod resume;
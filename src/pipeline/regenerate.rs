@@ -0,0 +1,80 @@
+use colored::Colorize;
+use eyre::{Result, eyre};
+use log::info;
+
+use crate::analysis::fingerprint::TechFingerprint;
+use crate::chat::agent::{GenerationInput, LLMResumeOutput, ResumeAgent};
+use crate::chat::section::{OutputSection, OutputTarget};
+use crate::pipeline::artifacts::{RunDir, Stage};
+use crate::scraper::forge::RepoData;
+use crate::scraper::github::ExternalContribution;
+use crate::scraper::job::JobDescription;
+use crate::utils::cli::{Args, RegenerateArgs};
+use crate::utils::config::Config;
+
+/// Regenerates one section or entry of an earlier run's content and saves it
+/// back, so the run can be reassembled from the `assemble` stage.
+pub async fn run_regenerate(
+    args: &Args,
+    regenerate: &RegenerateArgs,
+    config: Config,
+) -> Result<()> {
+    let run = RunDir::resume(regenerate.run.clone(), Some(Stage::Assemble))?;
+    let language = run.language(args.language.as_ref())?;
+    let job_description: JobDescription = run.load(Stage::Job)?;
+    let contributions: Vec<ExternalContribution> = run.load(Stage::Contributions)?;
+    let selected_repos: Vec<RepoData> = run.load(Stage::Select)?;
+    let fingerprints: Vec<TechFingerprint> = run.load(Stage::Analyze)?;
+    let output: LLMResumeOutput = run.load(Stage::Generate)?;
+
+    let section = regenerate.section;
+    let target = match &regenerate.entry {
+        Some(entry) => {
+            let index = section.find(&output, entry).ok_or_else(|| {
+                eyre!(
+                    "no {} entry matches '{}'",
+                    section.label().to_lowercase(),
+                    entry
+                )
+            })?;
+            OutputTarget::Entry(section, index)
+        }
+        None => OutputTarget::Section(section),
+    };
+
+    let agent =
+        ResumeAgent::from_config(&config.llm)?.with_private_links(config.github.private_links);
+    let input = GenerationInput {
+        resume_config: &config.resume,
+        job_description: &job_description,
+        github_repos: &selected_repos,
+        contributions: &contributions,
+        fingerprints: &fingerprints,
+        language: &language,
+    };
+    let output = agent
+        .regenerate(&input, &output, target, regenerate.feedback.as_deref())
+        .await?;
+
+    run.save(Stage::Generate, &output)?;
+    print_section(section, &output);
+    agent.usage().print_summary();
+
+    info!(
+        "rebuild the resume with: auto-resume --resume-from {} --from-stage assemble",
+        regenerate.run.display()
+    );
+
+    Ok(())
+}
+
+fn print_section(section: OutputSection, output: &LLMResumeOutput) {
+    println!("\n{}", format!("=== {} ===", section.label()).cyan().bold());
+
+    for index in 0..section.count(output) {
+        println!("{}", section.title(output, index).bold());
+        for bullet in section.bullets(output, index) {
+            println!("  - {}", bullet);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use eyre::Result;
+use log::info;
+use tectonic::latex_to_pdf;
+
+use crate::chat::agent::{
+    GenerationInput, LLMResumeOutput, ResumeAgent, resume_output_to_resume_items,
+};
+use crate::latex::assembler::{LatexResumeAssembler, ResumeLanguage};
+use crate::models::json_resume::JsonResume;
+use crate::render::docx::DocxResumeWriter;
+use crate::render::renderer::OutputFormat;
+use crate::utils::config::Config;
+
+/// Replaces the configured resume sections with the LLM-generated ones,
+/// keeping the configured values for any section the LLM left empty.
+pub fn apply_resume_output(config: &Config, output: &LLMResumeOutput) -> Config {
+    let (skills, experience, projects, education, contributions) =
+        resume_output_to_resume_items(output);
+
+    Arc::new({
+        let mut cfg = (**config).clone();
+        cfg.resume.skills = if skills.items.is_empty() {
+            cfg.resume.skills
+        } else {
+            vec![skills]
+        };
+        cfg.resume.experience = if experience.is_empty() {
+            cfg.resume.experience
+        } else {
+            experience
+        };
+        cfg.resume.projects = if projects.is_empty() {
+            cfg.resume.projects
+        } else {
+            projects
+        };
+        cfg.resume.education = if education.is_empty() {
+            cfg.resume.education
+        } else {
+            education
+        };
+        cfg.resume.contributions = if contributions.is_empty() {
+            cfg.resume.contributions
+        } else {
+            contributions
+        };
+        cfg
+    })
+}
+
+pub async fn compile_pdf(latex: String) -> Result<Vec<u8>> {
+    tokio::task::spawn_blocking(|| latex_to_pdf(latex))
+        .await?
+        .map_err(|e| {
+            eprintln!("Tectonic error details: {:#?}", e);
+            eyre::eyre!("failed to compile LaTeX document: {}", e.description())
+        })
+}
+
+/// Renders the resume in `format` and writes it next to `output`, with the
+/// format's extension. Returns the path written.
+pub async fn write_rendered(
+    format: OutputFormat,
+    config: Config,
+    language: ResumeLanguage,
+    output: &Path,
+) -> Result<PathBuf> {
+    let (path, document) = match format.renderer(config.clone(), language.clone()) {
+        Some(renderer) => (
+            output.with_extension(renderer.extension()),
+            renderer.render()?.into_bytes(),
+        ),
+        None => (
+            output.with_extension("docx"),
+            DocxResumeWriter::new(config, language).write()?,
+        ),
+    };
+
+    tokio::fs::write(&path, document).await?;
+    Ok(path)
+}
+
+/// Writes the LLM output, with the personal details from the config, as a
+/// JSON Resume document.
+pub async fn export_json_resume(
+    config: &Config,
+    output: &LLMResumeOutput,
+    path: &Path,
+) -> Result<()> {
+    let json_resume = JsonResume::from_output(&config.resume, output);
+    tokio::fs::write(path, serde_json::to_string_pretty(&json_resume)?).await?;
+    Ok(())
+}
+
+/// Where the cover letter for a resume goes: `resume.pdf` -> `resume-cover-letter.pdf`
+pub fn cover_letter_path(output: &Path) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "resume".to_string());
+    output.with_file_name(format!("{}-cover-letter.pdf", stem))
+}
+
+/// Generates a cover letter for the job and compiles it to a PDF at `path`,
+/// also saving its LaTeX source when `save_latex` is set.
+pub async fn write_cover_letter(
+    agent: &ResumeAgent,
+    input: &GenerationInput<'_>,
+    config: &Config,
+    output: &LLMResumeOutput,
+    path: &Path,
+    save_latex: bool,
+) -> Result<()> {
+    let letter = agent.generate_cover_letter(input, output).await?;
+    let latex = LatexResumeAssembler::new(config.clone(), input.language.clone())
+        .with_job(input.job_description)
+        .assemble_cover_letter(&letter)?;
+
+    if save_latex {
+        info!(
+            "saving cover letter LaTeX source to {}",
+            path.with_extension("tex").display()
+        );
+        tokio::fs::write(path.with_extension("tex"), &latex).await?;
+    }
+
+    info!("compiling cover letter to PDF");
+    tokio::fs::write(path, compile_pdf(latex).await?).await?;
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use std::io::Cursor;
+
+use docx_rs::{
+    AbstractNumbering, AlignmentType, Docx, Hyperlink, HyperlinkType, IndentLevel, Level, LevelJc,
+    LevelText, LineSpacing, NumberFormat, Numbering, NumberingId, PageMargin, Paragraph, Run,
+    RunFonts, SpecialIndentType, Start, Style, StyleType, Tab, TabValueType,
+};
+use eyre::Result;
+
+use crate::latex::assembler::ResumeLanguage;
+use crate::render::inline::{Inline, parse_inline};
+use crate::render::renderer::{contacts, resume_sections};
+use crate::utils::config::{Config, ResumeItem};
+
+// A4 with the same 0.5in margins as the LaTeX template, in twentieths of a point
+const PAGE_WIDTH: u32 = 11906;
+const PAGE_HEIGHT: u32 = 16838;
+const MARGIN: i32 = 720;
+const TEXT_WIDTH: usize = PAGE_WIDTH as usize - 2 * MARGIN as usize;
+
+const FONT: &str = "Times New Roman";
+const CODE_FONT: &str = "Courier New";
+const BULLET_NUMBERING: usize = 1;
+
+/// Writes the resume as a native Word document, with real heading styles
+/// and bullet lists so ATS parsers can read it.
+pub struct DocxResumeWriter {
+    language: ResumeLanguage,
+    config: Config,
+}
+
+impl DocxResumeWriter {
+    pub fn new(config: Config, language: impl Into<Option<ResumeLanguage>>) -> Self {
+        Self {
+            language: language.into().unwrap_or_default(),
+            config,
+        }
+    }
+
+    pub fn write(&self) -> Result<Vec<u8>> {
+        let resume = &self.config.resume;
+
+        let mut docx = Self::document()
+            .add_paragraph(
+                Paragraph::new()
+                    .style("Title")
+                    .add_run(Run::new().add_text(&resume.full_name)),
+            )
+            .add_paragraph(self.header());
+
+        for section in resume_sections(resume, &self.language) {
+            if section.items.is_empty() {
+                continue;
+            }
+
+            docx = docx.add_paragraph(
+                Paragraph::new()
+                    .style("Heading1")
+                    .add_run(Run::new().add_text(section.title.to_uppercase())),
+            );
+
+            for item in section.items {
+                for paragraph in Self::item(item) {
+                    docx = docx.add_paragraph(paragraph);
+                }
+            }
+        }
+
+        let mut buffer = Cursor::new(Vec::new());
+        docx.build().pack(&mut buffer)?;
+        Ok(buffer.into_inner())
+    }
+
+    /// Empty document with the page setup, styles and bullet numbering.
+    fn document() -> Docx {
+        let fonts = || RunFonts::new().ascii(FONT).hi_ansi(FONT);
+
+        Docx::new()
+            .page_size(PAGE_WIDTH, PAGE_HEIGHT)
+            .page_margin(
+                PageMargin::new()
+                    .top(MARGIN)
+                    .bottom(MARGIN)
+                    .left(MARGIN)
+                    .right(MARGIN),
+            )
+            .default_fonts(fonts())
+            .default_size(20)
+            .add_style(
+                Style::new("Title", StyleType::Paragraph)
+                    .name("Title")
+                    .fonts(fonts())
+                    .size(40)
+                    .bold()
+                    .align(AlignmentType::Center),
+            )
+            .add_style(
+                Style::new("Heading1", StyleType::Paragraph)
+                    .name("Heading 1")
+                    .fonts(fonts())
+                    .size(24)
+                    .bold()
+                    .outline_lvl(0)
+                    .line_spacing(LineSpacing::new().before(240).after(120)),
+            )
+            .add_abstract_numbering(
+                AbstractNumbering::new(BULLET_NUMBERING).add_level(
+                    Level::new(
+                        0,
+                        Start::new(1),
+                        NumberFormat::new("bullet"),
+                        LevelText::new("•"),
+                        LevelJc::new("left"),
+                    )
+                    .indent(
+                        Some(360),
+                        Some(SpecialIndentType::Hanging(360)),
+                        None,
+                        None,
+                    ),
+                ),
+            )
+            .add_numbering(Numbering::new(BULLET_NUMBERING, BULLET_NUMBERING))
+    }
+
+    fn header(&self) -> Paragraph {
+        let resume = &self.config.resume;
+        let mut paragraph = Paragraph::new()
+            .align(AlignmentType::Center)
+            .add_run(Run::new().add_text(format!("{}, {}", resume.city, resume.country)));
+
+        for contact in contacts(resume) {
+            paragraph = paragraph.add_run(Run::new().add_text(" | "));
+            paragraph = match contact.link {
+                Some(link) => paragraph.add_hyperlink(
+                    Hyperlink::new(link, HyperlinkType::External)
+                        .add_run(Run::new().add_text(contact.text)),
+                ),
+                None => paragraph.add_run(Run::new().add_text(contact.text)),
+            };
+        }
+
+        paragraph
+    }
+
+    fn item(item: &ResumeItem) -> Vec<Paragraph> {
+        let mut paragraphs = Vec::new();
+        let right_tab = || Tab::new().val(TabValueType::Right).pos(TEXT_WIDTH);
+
+        if let Some(title) = &item.title {
+            let mut paragraph = Self::add_inline(
+                Paragraph::new().add_tab(right_tab()),
+                &parse_inline(title),
+                true,
+                false,
+            );
+
+            if let Some(location) = &item.location {
+                paragraph = paragraph.add_run(Run::new().add_tab());
+                paragraph = match &item.link {
+                    Some(link) => paragraph.add_hyperlink(
+                        Hyperlink::new(link, HyperlinkType::External)
+                            .add_run(Run::new().add_text(location)),
+                    ),
+                    None => paragraph.add_run(Run::new().add_text(location)),
+                };
+            }
+
+            paragraphs.push(paragraph);
+        }
+
+        if let Some(description) = &item.description {
+            let mut paragraph = Self::add_inline(
+                Paragraph::new().add_tab(right_tab()),
+                &parse_inline(description),
+                false,
+                true,
+            );
+
+            if let Some(date) = &item.date {
+                paragraph = paragraph.add_run(Run::new().add_tab().add_text(date));
+            }
+
+            paragraphs.push(paragraph);
+        }
+
+        for bullet in &item.items {
+            paragraphs.push(Self::add_inline(
+                Paragraph::new().numbering(NumberingId::new(BULLET_NUMBERING), IndentLevel::new(0)),
+                &parse_inline(bullet),
+                false,
+                false,
+            ));
+        }
+
+        paragraphs
+    }
+
+    /// Appends inline markup to `paragraph` as styled runs.
+    fn add_inline(
+        mut paragraph: Paragraph,
+        fragments: &[Inline],
+        bold: bool,
+        italic: bool,
+    ) -> Paragraph {
+        for fragment in fragments {
+            let run = match fragment {
+                Inline::Text(text) => Run::new().add_text(text),
+                Inline::Code(raw) => Run::new()
+                    .add_text(raw)
+                    .fonts(RunFonts::new().ascii(CODE_FONT).hi_ansi(CODE_FONT)),
+                Inline::Bold(inner) => {
+                    paragraph = Self::add_inline(paragraph, inner, true, italic);
+                    continue;
+                }
+            };
+
+            let run = if bold { run.bold() } else { run };
+            let run = if italic { run.italic() } else { run };
+            paragraph = paragraph.add_run(run);
+        }
+
+        paragraph
+    }
+}
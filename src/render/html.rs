@@ -0,0 +1,152 @@
+use eyre::Result;
+
+use crate::latex::assembler::ResumeLanguage;
+use crate::render::inline::InlineMarkup;
+use crate::render::renderer::{ResumeRenderer, contacts, resume_sections};
+use crate::utils::config::{Config, ResumeItem};
+
+static STYLE: &str = "\
+body { font-family: 'Times New Roman', serif; max-width: 820px; margin: 2em auto; padding: 0 1em; color: #111; }
+header { text-align: center; }
+h1 { margin-bottom: 0.2em; }
+h2 { text-transform: uppercase; font-size: 1.1em; border-bottom: 1px solid #111; margin-top: 1.4em; }
+.item { margin-bottom: 0.8em; }
+.row { display: flex; justify-content: space-between; }
+ul { margin: 0.2em 0; padding-left: 1.2em; }
+a { color: inherit; }";
+
+pub struct Html;
+
+impl InlineMarkup for Html {
+    fn escape(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => result.push_str("&amp;"),
+                '<' => result.push_str("&lt;"),
+                '>' => result.push_str("&gt;"),
+                '"' => result.push_str("&quot;"),
+                '\'' => result.push_str("&#39;"),
+                _ => result.push(c),
+            }
+        }
+        result
+    }
+
+    fn bold(inner: String) -> String {
+        format!("<strong>{}</strong>", inner)
+    }
+
+    fn code(raw: &str) -> String {
+        format!("<code>{}</code>", Self::escape(raw))
+    }
+}
+
+pub struct HtmlResumeRenderer {
+    language: ResumeLanguage,
+    config: Config,
+}
+
+impl HtmlResumeRenderer {
+    pub fn new(config: Config, language: impl Into<Option<ResumeLanguage>>) -> Self {
+        Self {
+            language: language.into().unwrap_or_default(),
+            config,
+        }
+    }
+
+    fn header(&self) -> String {
+        let resume = &self.config.resume;
+        let mut parts = vec![format!(
+            "{}, {}",
+            Html::escape(&resume.city),
+            Html::escape(&resume.country)
+        )];
+
+        parts.extend(
+            contacts(resume)
+                .into_iter()
+                .map(|contact| match contact.link {
+                    Some(link) => format!(
+                        "<a href=\"{}\">{}</a>",
+                        Html::escape(&link),
+                        Html::escape(&contact.text)
+                    ),
+                    None => Html::escape(&contact.text),
+                }),
+        );
+
+        parts.join(" | ")
+    }
+
+    fn item(item: &ResumeItem) -> String {
+        let mut out = String::from("<div class=\"item\">\n");
+
+        if let Some(title) = &item.title {
+            let location = match (&item.location, &item.link) {
+                (Some(location), Some(link)) => format!(
+                    "<a href=\"{}\">{}</a>",
+                    Html::escape(link),
+                    Html::escape(location)
+                ),
+                (Some(location), None) => Html::escape(location),
+                (None, _) => String::new(),
+            };
+
+            out.push_str(&format!(
+                "<div class=\"row\"><strong>{}</strong><span>{}</span></div>\n",
+                Html::inline(title),
+                location
+            ));
+        }
+
+        if let Some(description) = &item.description {
+            out.push_str(&format!(
+                "<div class=\"row\"><em>{}</em><span>{}</span></div>\n",
+                Html::inline(description),
+                item.date.as_deref().map(Html::escape).unwrap_or_default()
+            ));
+        }
+
+        out.push_str("<ul>\n");
+        for bullet in &item.items {
+            out.push_str(&format!("  <li>{}</li>\n", Html::inline(bullet)));
+        }
+        out.push_str("</ul>\n</div>\n");
+
+        out
+    }
+}
+
+impl ResumeRenderer for HtmlResumeRenderer {
+    fn render(&self) -> Result<String> {
+        let resume = &self.config.resume;
+        let lang = self.language.code();
+
+        let mut body = String::new();
+        for section in resume_sections(resume, &self.language) {
+            if section.items.is_empty() {
+                continue;
+            }
+
+            body.push_str(&format!(
+                "<section id=\"{}\">\n<h2>{}</h2>\n{}</section>\n",
+                section.key.to_lowercase(),
+                Html::escape(section.title),
+                section.items.iter().map(Self::item).collect::<String>()
+            ));
+        }
+
+        Ok(format!(
+            "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{name}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n\
+             <header>\n<h1>{name}</h1>\n<p>{header}</p>\n</header>\n{body}</body>\n</html>\n",
+            name = Html::escape(&resume.full_name),
+            header = self.header(),
+        ))
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+}
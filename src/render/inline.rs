@@ -0,0 +1,97 @@
+/// A fragment of the lightweight inline markup used in resume text:
+/// `**bold**` (which may nest code) and `` `code` ``.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    Text(String),
+    Bold(Vec<Inline>),
+    Code(String),
+}
+
+/// Parses inline markup. Unterminated markers are kept as literal text.
+pub fn parse_inline(text: &str) -> Vec<Inline> {
+    let mut result = Vec::new();
+    let mut plain = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '*' && chars.peek() == Some(&'*') {
+            chars.next();
+            let mut bold_text = String::new();
+            let mut found_end = false;
+
+            while let Some(bc) = chars.next() {
+                if bc == '*' && chars.peek() == Some(&'*') {
+                    chars.next();
+                    found_end = true;
+                    break;
+                }
+                bold_text.push(bc);
+            }
+
+            if found_end {
+                flush(&mut result, &mut plain);
+                result.push(Inline::Bold(parse_inline(&bold_text)));
+            } else {
+                plain.push_str("**");
+                flush(&mut result, &mut plain);
+                result.extend(parse_inline(&bold_text));
+            }
+        } else if c == '`' {
+            let mut code_text = String::new();
+            let mut found_end = false;
+
+            for bc in chars.by_ref() {
+                if bc == '`' {
+                    found_end = true;
+                    break;
+                }
+                code_text.push(bc);
+            }
+
+            if found_end {
+                flush(&mut result, &mut plain);
+                result.push(Inline::Code(code_text));
+            } else {
+                plain.push('`');
+                flush(&mut result, &mut plain);
+                result.extend(parse_inline(&code_text));
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+
+    flush(&mut result, &mut plain);
+    result
+}
+
+fn flush(result: &mut Vec<Inline>, plain: &mut String) {
+    if !plain.is_empty() {
+        result.push(Inline::Text(std::mem::take(plain)));
+    }
+}
+
+/// How an output format spells inline markup.
+pub trait InlineMarkup {
+    fn escape(text: &str) -> String;
+
+    fn bold(inner: String) -> String;
+
+    fn code(raw: &str) -> String;
+
+    /// Parses `text` and renders it in this format.
+    fn inline(text: &str) -> String {
+        Self::render(&parse_inline(text))
+    }
+
+    fn render(fragments: &[Inline]) -> String {
+        fragments
+            .iter()
+            .map(|fragment| match fragment {
+                Inline::Text(text) => Self::escape(text),
+                Inline::Bold(inner) => Self::bold(Self::render(inner)),
+                Inline::Code(raw) => Self::code(raw),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,133 @@
+use eyre::Result;
+
+use crate::latex::assembler::ResumeLanguage;
+use crate::render::inline::InlineMarkup;
+use crate::render::renderer::{ResumeRenderer, contacts, resume_sections};
+use crate::utils::config::{Config, ResumeItem};
+
+pub struct Markdown;
+
+impl InlineMarkup for Markdown {
+    fn escape(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        for c in text.chars() {
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#') {
+                result.push('\\');
+            }
+            result.push(c);
+        }
+        result
+    }
+
+    fn bold(inner: String) -> String {
+        format!("**{}**", inner)
+    }
+
+    fn code(raw: &str) -> String {
+        format!("`{}`", raw)
+    }
+}
+
+pub struct MarkdownResumeRenderer {
+    language: ResumeLanguage,
+    config: Config,
+}
+
+impl MarkdownResumeRenderer {
+    pub fn new(config: Config, language: impl Into<Option<ResumeLanguage>>) -> Self {
+        Self {
+            language: language.into().unwrap_or_default(),
+            config,
+        }
+    }
+
+    fn header(&self) -> String {
+        let resume = &self.config.resume;
+        let mut parts = vec![format!(
+            "{}, {}",
+            Markdown::escape(&resume.city),
+            Markdown::escape(&resume.country)
+        )];
+
+        parts.extend(
+            contacts(resume)
+                .into_iter()
+                .map(|contact| match contact.link {
+                    Some(link) => format!("[{}]({})", Markdown::escape(&contact.text), link),
+                    None => Markdown::escape(&contact.text),
+                }),
+        );
+
+        parts.join(" | ")
+    }
+
+    fn item(item: &ResumeItem) -> String {
+        let mut out = String::new();
+
+        if let Some(title) = &item.title {
+            let mut heading = format!("### {}", Markdown::inline(title));
+
+            if let Some(location) = &item.location {
+                match &item.link {
+                    Some(link) => {
+                        heading.push_str(&format!(" — [{}]({})", Markdown::escape(location), link))
+                    }
+                    None => heading.push_str(&format!(" — {}", Markdown::escape(location))),
+                }
+            }
+
+            out.push_str(&heading);
+            out.push_str("\n\n");
+        }
+
+        if let Some(description) = &item.description {
+            let mut line = format!("*{}*", Markdown::inline(description));
+
+            if let Some(date) = &item.date {
+                line.push_str(&format!(" · {}", Markdown::escape(date)));
+            }
+
+            out.push_str(&line);
+            out.push_str("\n\n");
+        }
+
+        for bullet in &item.items {
+            out.push_str(&format!("- {}\n", Markdown::inline(bullet)));
+        }
+
+        out
+    }
+}
+
+impl ResumeRenderer for MarkdownResumeRenderer {
+    fn render(&self) -> Result<String> {
+        let resume = &self.config.resume;
+        let mut out = format!(
+            "# {}\n\n{}\n",
+            Markdown::escape(&resume.full_name),
+            self.header()
+        );
+
+        for section in resume_sections(resume, &self.language) {
+            if section.items.is_empty() {
+                continue;
+            }
+
+            out.push_str(&format!("\n## {}\n\n", section.title));
+            out.push_str(
+                &section
+                    .items
+                    .iter()
+                    .map(Self::item)
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            );
+        }
+
+        Ok(out)
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+}
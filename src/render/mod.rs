@@ -0,0 +1,6 @@
+pub mod docx;
+pub mod html;
+pub mod inline;
+pub mod markdown;
+pub mod renderer;
+pub mod text;
@@ -0,0 +1,115 @@
+use clap::ValueEnum;
+use eyre::Result;
+
+use crate::latex::assembler::{LatexResumeAssembler, ResumeLanguage};
+use crate::render::html::HtmlResumeRenderer;
+use crate::render::markdown::MarkdownResumeRenderer;
+use crate::render::text::TextResumeRenderer;
+use crate::utils::config::{Config, ResumeConfig, ResumeItem};
+use crate::utils::misc::strip_url;
+
+/// Turns the resume configuration into a document in some output format.
+pub trait ResumeRenderer {
+    fn render(&self) -> Result<String>;
+
+    /// File extension of the rendered document
+    fn extension(&self) -> &'static str;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// LaTeX compiled to PDF
+    #[default]
+    #[value(alias = "pdf")]
+    Latex,
+    /// Markdown, for pasting into ATS portals
+    #[value(alias = "md")]
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// Plain text
+    #[value(alias = "txt")]
+    Text,
+    /// Word document, for ATS portals that require uploads
+    Docx,
+}
+
+impl OutputFormat {
+    /// Renderer for text-based formats; `None` for binary ones (DOCX).
+    pub fn renderer(
+        &self,
+        config: Config,
+        language: ResumeLanguage,
+    ) -> Option<Box<dyn ResumeRenderer>> {
+        match self {
+            OutputFormat::Latex => Some(Box::new(LatexResumeAssembler::new(config, language))),
+            OutputFormat::Markdown => Some(Box::new(MarkdownResumeRenderer::new(config, language))),
+            OutputFormat::Html => Some(Box::new(HtmlResumeRenderer::new(config, language))),
+            OutputFormat::Text => Some(Box::new(TextResumeRenderer::new(config, language))),
+            OutputFormat::Docx => None,
+        }
+    }
+}
+
+/// A titled resume section, in the order every format lays them out.
+pub struct ResumeSection<'a> {
+    pub key: &'static str,
+    pub title: &'static str,
+    pub items: &'a [ResumeItem],
+}
+
+pub fn resume_sections<'a>(
+    resume: &'a ResumeConfig,
+    language: &ResumeLanguage,
+) -> Vec<ResumeSection<'a>> {
+    [
+        ("EDUCATION", resume.education.as_slice()),
+        ("SKILLS", resume.skills.as_slice()),
+        ("EXPERIENCE", resume.experience.as_slice()),
+        ("PROJECTS", resume.projects.as_slice()),
+        ("CONTRIBUTIONS", resume.contributions.as_slice()),
+    ]
+    .into_iter()
+    .map(|(key, items)| ResumeSection {
+        key,
+        title: language.section_title(key),
+        items,
+    })
+    .collect()
+}
+
+/// A contact detail shown under the name, with an optional link target.
+pub struct Contact {
+    pub text: String,
+    pub link: Option<String>,
+}
+
+pub fn contacts(resume: &ResumeConfig) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+
+    if let Some(email) = &resume.email {
+        contacts.push(Contact {
+            text: email.clone(),
+            link: Some(format!("mailto:{}", email)),
+        });
+    }
+
+    if let Some(phone) = &resume.phone {
+        contacts.push(Contact {
+            text: phone.clone(),
+            link: None,
+        });
+    }
+
+    for url in [&resume.linkedin, &resume.github, &resume.site]
+        .into_iter()
+        .flatten()
+    {
+        contacts.push(Contact {
+            text: strip_url(url).to_string(),
+            link: Some(url.clone()),
+        });
+    }
+
+    contacts
+}
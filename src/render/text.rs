@@ -0,0 +1,120 @@
+use eyre::Result;
+
+use crate::latex::assembler::ResumeLanguage;
+use crate::render::inline::InlineMarkup;
+use crate::render::renderer::{ResumeRenderer, contacts, resume_sections};
+use crate::utils::config::{Config, ResumeItem};
+
+pub struct PlainText;
+
+impl InlineMarkup for PlainText {
+    fn escape(text: &str) -> String {
+        text.to_string()
+    }
+
+    fn bold(inner: String) -> String {
+        inner
+    }
+
+    fn code(raw: &str) -> String {
+        raw.to_string()
+    }
+}
+
+/// Plain-text resume, laid out for pasting into ATS application forms.
+pub struct TextResumeRenderer {
+    language: ResumeLanguage,
+    config: Config,
+}
+
+impl TextResumeRenderer {
+    pub fn new(config: Config, language: impl Into<Option<ResumeLanguage>>) -> Self {
+        Self {
+            language: language.into().unwrap_or_default(),
+            config,
+        }
+    }
+
+    fn item(item: &ResumeItem) -> String {
+        let mut out = String::new();
+
+        let title_line = [
+            item.title.as_deref().map(PlainText::inline),
+            item.location.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(" | ");
+
+        if !title_line.is_empty() {
+            out.push_str(&title_line);
+            if let Some(link) = &item.link {
+                out.push_str(&format!(" ({})", link));
+            }
+            out.push('\n');
+        }
+
+        let description_line = [
+            item.description.as_deref().map(PlainText::inline),
+            item.date.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(" | ");
+
+        if !description_line.is_empty() {
+            out.push_str(&description_line);
+            out.push('\n');
+        }
+
+        for bullet in &item.items {
+            out.push_str(&format!("  - {}\n", PlainText::inline(bullet)));
+        }
+
+        out
+    }
+}
+
+impl ResumeRenderer for TextResumeRenderer {
+    fn render(&self) -> Result<String> {
+        let resume = &self.config.resume;
+
+        let mut header = vec![format!("{}, {}", resume.city, resume.country)];
+        header.extend(contacts(resume).into_iter().map(|contact| contact.text));
+
+        let mut out = format!(
+            "{}\n{}\n",
+            resume.full_name.to_uppercase(),
+            header.join(" | ")
+        );
+
+        for section in resume_sections(resume, &self.language) {
+            if section.items.is_empty() {
+                continue;
+            }
+
+            let title = section.title.to_uppercase();
+            out.push_str(&format!(
+                "\n{}\n{}\n",
+                title,
+                "=".repeat(title.chars().count())
+            ));
+            out.push_str(
+                &section
+                    .items
+                    .iter()
+                    .map(Self::item)
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            );
+        }
+
+        Ok(out)
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+}
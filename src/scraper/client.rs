@@ -0,0 +1,314 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use eyre::{Result, eyre};
+use log::{debug, warn};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+
+use crate::utils::cache::{self, CachedResponse};
+use crate::utils::config::GithubConfig;
+
+/// Requests in flight at once per host, since GitHub answers bursts of
+/// concurrent requests with secondary rate limits.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Tries for a request that hits a rate limit.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// First wait on a secondary rate limit without `Retry-After`, doubled on
+/// every further try.
+const SECONDARY_LIMIT_DELAY: Duration = Duration::from_secs(60);
+
+/// Longest wait for a rate limit to reset before giving up.
+const MAX_WAIT: Duration = Duration::from_secs(5 * 60);
+
+/// Requests left in the primary rate limit at which a warning is logged.
+const LOW_REMAINING: u64 = 10;
+
+/// Errors that every further request to the host would run into too, so
+/// scraping stops on them instead of skipping the repository at hand.
+#[derive(Debug)]
+pub enum HostError {
+    RateLimited(String),
+    Unauthorized(String),
+}
+
+impl std::fmt::Display for HostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HostError::RateLimited(message) | HostError::Unauthorized(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HostError {}
+
+impl HostError {
+    /// Whether `error` is a rate limit or authentication failure.
+    pub fn is_host_error(error: &eyre::Report) -> bool {
+        error.downcast_ref::<HostError>().is_some()
+    }
+}
+
+/// An API response, possibly replayed from the ETag cache.
+pub struct ApiResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+    url: String,
+    host: &'static str,
+}
+
+impl ApiResponse {
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.body).map_err(|e| {
+            eyre!(
+                "invalid {} API response from {}: {}",
+                self.host,
+                self.url,
+                e
+            )
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Whether the `link` header points at another page.
+    pub fn has_next_page(&self) -> bool {
+        self.header("link")
+            .is_some_and(|link| link.contains("rel=\"next\""))
+    }
+
+    /// Turns an unsuccessful status into an error carrying the host's message.
+    pub fn error_for_status(self) -> Result<Self> {
+        if self.status.is_success() {
+            return Ok(self);
+        }
+
+        let message = serde_json::from_str::<serde_json::Value>(&self.body)
+            .ok()
+            .and_then(|body| body["message"].as_str().map(str::to_string))
+            .unwrap_or(self.body);
+        let message = format!(
+            "{} API error ({}) for {}: {}",
+            self.host, self.status, self.url, message
+        );
+        if self.status == StatusCode::UNAUTHORIZED {
+            return Err(HostError::Unauthorized(message).into());
+        }
+        Err(eyre!(message))
+    }
+}
+
+/// HTTP layer shared by every forge API request: authentication,
+/// conditional requests with stored ETags and waiting out rate limits.
+pub struct ApiClient {
+    client: reqwest::Client,
+    /// Name of the host in messages
+    host: &'static str,
+    /// Value of the `Authorization` header
+    authorization: Option<String>,
+    permits: Semaphore,
+    /// Whether the low rate limit warning was logged already
+    warned_low: AtomicBool,
+}
+
+impl ApiClient {
+    pub fn new(host: &'static str, authorization: Option<String>) -> Self {
+        ApiClient {
+            client: reqwest::Client::new(),
+            host,
+            authorization,
+            permits: Semaphore::new(MAX_CONCURRENT_REQUESTS),
+            warned_low: AtomicBool::new(false),
+        }
+    }
+
+    pub fn github(config: &GithubConfig) -> Self {
+        ApiClient::new(
+            "GitHub",
+            config
+                .token
+                .as_ref()
+                .map(|token| format!("token {}", token)),
+        )
+    }
+
+    /// Sends a GET request, with `If-None-Match` when an earlier response was
+    /// stored, so unchanged data is served from the cache and doesn't count
+    /// against the quota.
+    pub async fn get(&self, url: &str) -> Result<ApiResponse> {
+        let cached = cache::get_cached_response(url, self.authorization.as_deref());
+
+        let response = self
+            .send(url, || {
+                let req = self.client.get(url);
+                match &cached {
+                    Some(cached) => req.header("If-None-Match", &cached.etag),
+                    None => req,
+                }
+            })
+            .await?;
+
+        match (response.status, cached) {
+            (StatusCode::NOT_MODIFIED, Some(cached)) => {
+                debug!("{} not modified, using cached response", url);
+                let mut headers = response.headers;
+                if let Some(link) = cached.link.as_deref().and_then(|link| link.parse().ok()) {
+                    headers.insert("link", link);
+                }
+                Ok(ApiResponse {
+                    status: StatusCode::OK,
+                    headers,
+                    body: cached.body,
+                    url: response.url,
+                    host: self.host,
+                })
+            }
+            (StatusCode::OK, _) => {
+                if let Some(etag) = response.header("etag") {
+                    let stored = CachedResponse {
+                        etag: etag.to_string(),
+                        link: response.header("link").map(str::to_string),
+                        body: response.body.clone(),
+                    };
+                    if let Err(e) =
+                        cache::cache_response(url, self.authorization.as_deref(), &stored)
+                    {
+                        debug!("failed to cache response for {}: {}", url, e);
+                    }
+                }
+                Ok(response)
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Sends a POST request with a JSON body (GraphQL queries).
+    pub async fn post(&self, url: &str, body: &serde_json::Value) -> Result<ApiResponse> {
+        self.send(url, || self.client.post(url).json(body)).await
+    }
+
+    /// Sends the request built by `build`, waiting and retrying while the
+    /// host reports a rate limit.
+    async fn send(
+        &self,
+        url: &str,
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<ApiResponse> {
+        let _permit = self.permits.acquire().await?;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut req = build().header("User-Agent", "auto-resume-app");
+            if let Some(authorization) = &self.authorization {
+                req = req.header("Authorization", authorization);
+            }
+
+            let response = req.send().await?;
+            let response = ApiResponse {
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.text().await?,
+                url: url.to_string(),
+                host: self.host,
+            };
+
+            let remaining = response
+                .header("x-ratelimit-remaining")
+                .and_then(|remaining| remaining.parse::<u64>().ok());
+            // concurrent requests can skip past the threshold, so any count
+            // at or below it warns, once
+            if let Some(remaining) = remaining.filter(|remaining| *remaining <= LOW_REMAINING)
+                && !self.warned_low.swap(true, Ordering::Relaxed)
+            {
+                warn!(
+                    "{} {} API requests left until the rate limit resets{}",
+                    remaining,
+                    self.host,
+                    if self.authorization.is_none() {
+                        " (set a token for a higher limit)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+
+            let Some(wait) = rate_limit_wait(&response, attempt) else {
+                return Ok(response);
+            };
+
+            if wait > MAX_WAIT || attempt == MAX_ATTEMPTS {
+                return Err(HostError::RateLimited(format!(
+                    "{} API rate limit exceeded for {} (resets in {}s){}",
+                    self.host,
+                    url,
+                    wait.as_secs(),
+                    if self.authorization.is_none() {
+                        ", set a token for a higher limit"
+                    } else {
+                        ""
+                    }
+                ))
+                .into());
+            }
+
+            warn!(
+                "{} API rate limit hit, retrying in {}s (attempt {}/{})",
+                self.host,
+                wait.as_secs(),
+                attempt,
+                MAX_ATTEMPTS
+            );
+            tokio::time::sleep(wait).await;
+        }
+
+        unreachable!("the last attempt returns")
+    }
+}
+
+/// How long to wait before retrying a rate-limited response, or `None` when
+/// the response isn't rate limited. Follows GitHub's guidance: `Retry-After`
+/// first (also sent by GitLab and Gitea), then the primary limit's reset time,
+/// then exponential backoff for secondary limits.
+fn rate_limit_wait(response: &ApiResponse, attempt: u32) -> Option<Duration> {
+    if !matches!(
+        response.status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+    ) {
+        return None;
+    }
+
+    if let Some(seconds) = response
+        .header("retry-after")
+        .and_then(|seconds| seconds.parse::<u64>().ok())
+    {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let backoff = SECONDARY_LIMIT_DELAY * 2u32.pow(attempt - 1);
+    if response.header("x-ratelimit-remaining") == Some("0") {
+        // without a usable reset time, back off as for a secondary limit
+        return Some(
+            response
+                .header("x-ratelimit-reset")
+                .and_then(|reset| reset.parse::<i64>().ok())
+                .map_or(backoff, |reset| {
+                    let seconds = (reset - chrono::Utc::now().timestamp()).max(0) as u64 + 1;
+                    Duration::from_secs(seconds)
+                }),
+        );
+    }
+
+    response
+        .body
+        .to_lowercase()
+        .contains("secondary rate limit")
+        .then_some(backoff)
+}
@@ -0,0 +1,166 @@
+use std::cmp::Reverse;
+use std::pin::Pin;
+
+use eyre::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::analysis::guard::repo_key;
+use crate::models::github::{Authorship, RepositoryLanguages};
+use crate::scraper::client::HostError;
+use crate::scraper::gitea::GiteaForge;
+use crate::scraper::github::GitHubForge;
+use crate::scraper::gitlab::GitLabForge;
+use crate::scraper::local::LocalForge;
+use crate::utils::cache;
+use crate::utils::config::Config;
+use crate::utils::misc::strip_url;
+
+/// A repository from any forge, in the shape ranking and generation expect.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepoData {
+    pub name: String,
+    /// GitHub API URL, or the web page on other forges; the name for
+    /// unpublished repositories
+    pub url: String,
+    pub stargazers_count: u64,
+    pub forks_count: u64,
+    /// Size in KB
+    pub size: u64,
+    pub importance_score: u64,
+    pub languages: Option<RepositoryLanguages>,
+    pub created_at: String,
+    pub pushed_at: String,
+    pub readme: Option<String>,
+    pub commits: u64,
+    #[serde(default)]
+    pub private: bool,
+    /// The user's own commits and lines, when GitHub has statistics for the repository
+    #[serde(default)]
+    pub authorship: Option<Authorship>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Host shown with the project on the resume, none for local
+    /// repositories without a URL
+    #[serde(default)]
+    pub forge: Option<String>,
+    /// A local repository with no public page, never linked on the resume
+    #[serde(default)]
+    pub unpublished: bool,
+}
+
+impl RepoData {
+    /// `owner/name` (host-qualified off github.com, see `repo_key`), unlike
+    /// `name` unique across owners and forges.
+    pub fn full_name(&self) -> String {
+        repo_key(&self.url)
+    }
+}
+
+/// Forges besides GitHub, configured under `[[config.forges]]`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    GitLab,
+    /// Gitea and its fork Forgejo, which runs Codeberg
+    #[serde(alias = "forgejo", alias = "codeberg")]
+    Gitea,
+}
+
+impl ForgeKind {
+    pub fn default_url(&self) -> &'static str {
+        match self {
+            ForgeKind::GitLab => "https://gitlab.com",
+            ForgeKind::Gitea => "https://codeberg.org",
+        }
+    }
+}
+
+/// Where a repository at `url` is hosted, as shown on the resume: the
+/// forge's name for well-known hosts, the host itself otherwise.
+pub fn host_label(url: &str) -> String {
+    let host = strip_url(url).split('/').next().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    match host {
+        "github.com" | "api.github.com" => "GitHub",
+        "gitlab.com" => "GitLab",
+        "codeberg.org" => "Codeberg",
+        "bitbucket.org" => "Bitbucket",
+        _ => host,
+    }
+    .to_string()
+}
+
+/// Waits for per-repository tasks, logging and skipping the repositories
+/// that failed. Rate limit and authentication errors stop the scrape, as
+/// every other repository would fail the same way.
+pub async fn join_repo_tasks<T>(tasks: Vec<(String, JoinHandle<Result<T>>)>) -> Result<Vec<T>> {
+    let mut results = Vec::with_capacity(tasks.len());
+    for (name, task) in tasks {
+        match task.await? {
+            Ok(result) => results.push(result),
+            Err(e) if HostError::is_host_error(&e) => return Err(e),
+            Err(e) => warn!("skipping repository {}: {}", name, e),
+        }
+    }
+
+    Ok(results)
+}
+
+pub type ScrapeFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<RepoData>>> + Send + 'a>>;
+
+/// A code host the user's repositories are scraped from.
+///
+/// Every forge maps its repositories into `RepoData`, so repositories from
+/// all hosts are ranked and selected together.
+pub trait SourceForge: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn scrape(&self) -> ScrapeFuture<'_>;
+}
+
+/// GitHub (unless its username is left empty), the configured forges and
+/// the local repositories.
+pub fn forges_from_config(config: &Config) -> Vec<Box<dyn SourceForge>> {
+    let mut forges: Vec<Box<dyn SourceForge>> = Vec::new();
+
+    if !config.github.username.is_empty() {
+        forges.push(Box::new(GitHubForge::new(config.clone())));
+    }
+
+    for forge in &config.forges {
+        forges.push(match forge.kind {
+            ForgeKind::GitLab => Box::new(GitLabForge::new(forge.clone())),
+            ForgeKind::Gitea => Box::new(GiteaForge::new(forge.clone())),
+        });
+    }
+
+    if !config.local_repos.is_empty() {
+        forges.push(Box::new(LocalForge::new(config.clone())));
+    }
+
+    forges
+}
+
+/// Scrapes the user's repositories from every source, most important first.
+pub async fn scrape_profile(config: &Config) -> Result<Vec<RepoData>> {
+    cache::init_cache()?;
+
+    let mut repos = Vec::new();
+    for forge in forges_from_config(config) {
+        info!("scraping {} profile", forge.name());
+        let mut forge_repos = forge.scrape().await?;
+        info!(
+            "found {} repositories on {}",
+            forge_repos.len(),
+            forge.name()
+        );
+        repos.append(&mut forge_repos);
+    }
+
+    repos.sort_by_key(|repo| Reverse(repo.importance_score));
+
+    info!("found {} repositories to analyze", repos.len());
+    Ok(repos)
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use eyre::Result;
+use log::{debug, info};
+
+use crate::models::gitea::{ContentsEntry, Repository};
+use crate::models::github::{RepositoryLanguages, importance_score};
+use crate::scraper::client::ApiClient;
+use crate::scraper::forge::{RepoData, ScrapeFuture, SourceForge, join_repo_tasks};
+use crate::utils::config::ForgeConfig;
+
+/// Largest page the Gitea API serves by default.
+const PAGE_SIZE: u32 = 50;
+
+pub struct GiteaScraper {
+    /// `{url}/api/v1`
+    api_url: String,
+    username: String,
+    client: ApiClient,
+}
+
+impl GiteaScraper {
+    pub fn new(forge: &ForgeConfig) -> Self {
+        GiteaScraper {
+            api_url: format!("{}/api/v1", forge.url()),
+            username: forge.username.clone(),
+            client: ApiClient::new(
+                "Gitea",
+                forge.token.as_ref().map(|token| format!("token {}", token)),
+            ),
+        }
+    }
+
+    /// Lists the user's repositories (private ones too with the user's own
+    /// token), forks excluded.
+    pub async fn list_repositories(&self) -> Result<Vec<Repository>> {
+        let mut page: u32 = 1;
+        let mut repositories: Vec<Repository> = Vec::new();
+        loop {
+            let response = self
+                .client
+                .get(&format!(
+                    "{}/users/{}/repos?limit={}&page={}",
+                    self.api_url, self.username, PAGE_SIZE, page
+                ))
+                .await?
+                .error_for_status()?;
+
+            repositories.append(&mut response.json()?);
+
+            match response.has_next_page() {
+                true => page += 1,
+                false => break,
+            }
+        }
+
+        repositories.retain(|repo| !repo.fork);
+        Ok(repositories)
+    }
+
+    /// Gitea has no README endpoint, so the README is looked up among the
+    /// files at the repository's root.
+    pub async fn get_readme(&self, repo: &Repository) -> Result<Option<String>> {
+        if repo.empty {
+            return Ok(None);
+        }
+
+        let entries: Vec<ContentsEntry> = self
+            .client
+            .get(&format!(
+                "{}/repos/{}/contents",
+                self.api_url, repo.full_name
+            ))
+            .await?
+            .error_for_status()?
+            .json()?;
+        let Some(readme) = entries
+            .iter()
+            .find(|entry| entry.kind == "file" && entry.name.to_lowercase().starts_with("readme"))
+        else {
+            return Ok(None);
+        };
+
+        let content = self
+            .client
+            .get(&format!(
+                "{}/repos/{}/raw/{}",
+                self.api_url, repo.full_name, readme.name
+            ))
+            .await?
+            .error_for_status()?
+            .body;
+
+        info!("Found README for repo: {}", repo.name);
+        Ok(Some(content))
+    }
+
+    /// Counts the default branch's commits from the total count header of a
+    /// one-commit page.
+    pub async fn get_commit_count(&self, repo: &Repository) -> Result<u64> {
+        if repo.empty {
+            return Ok(0);
+        }
+
+        let response = self
+            .client
+            .get(&format!(
+                "{}/repos/{}/commits?limit=1&stat=false&verification=false&files=false",
+                self.api_url, repo.full_name
+            ))
+            .await?
+            .error_for_status()?;
+
+        Ok(response
+            .header("x-total-count")
+            .and_then(|total| total.parse().ok())
+            .unwrap_or(0))
+    }
+
+    pub async fn get_languages(&self, repo: &Repository) -> Result<RepositoryLanguages> {
+        self.client
+            .get(&format!(
+                "{}/repos/{}/languages",
+                self.api_url, repo.full_name
+            ))
+            .await?
+            .error_for_status()?
+            .json()
+    }
+}
+
+/// A Gitea or Forgejo account, Codeberg included.
+pub struct GiteaForge {
+    config: ForgeConfig,
+}
+
+impl GiteaForge {
+    pub fn new(config: ForgeConfig) -> Self {
+        GiteaForge { config }
+    }
+}
+
+impl SourceForge for GiteaForge {
+    fn name(&self) -> &str {
+        self.config.host()
+    }
+
+    fn scrape(&self) -> ScrapeFuture<'_> {
+        Box::pin(scrape_repositories(&self.config))
+    }
+}
+
+async fn scrape_repositories(forge: &ForgeConfig) -> Result<Vec<RepoData>> {
+    let scraper = Arc::new(GiteaScraper::new(forge));
+    let label = forge.label();
+    let repos = scraper.list_repositories().await?;
+
+    debug!(
+        "fetching README and commit data for {} Gitea repositories (parallel)",
+        repos.len()
+    );
+
+    let mut tasks = Vec::new();
+    for repo in repos {
+        let scraper = Arc::clone(&scraper);
+        let name = repo.name.clone();
+        tasks.push((
+            name,
+            tokio::spawn(async move {
+                let readme = scraper.get_readme(&repo).await?;
+                let commits = scraper.get_commit_count(&repo).await?;
+                let languages = scraper.get_languages(&repo).await?;
+
+                Ok::<_, eyre::Report>(RepoData {
+                    name: repo.name.clone(),
+                    url: repo.html_url.clone(),
+                    stargazers_count: repo.stars_count,
+                    forks_count: repo.forks_count,
+                    size: repo.size,
+                    importance_score: importance_score(
+                        repo.stars_count,
+                        repo.forks_count,
+                        repo.size,
+                    ),
+                    languages: Some(languages),
+                    created_at: repo.created_at.clone(),
+                    pushed_at: repo.updated_at.clone(),
+                    readme: readme.filter(|readme| !readme.is_empty()),
+                    commits,
+                    private: repo.private,
+                    authorship: None,
+                    topics: repo.topics.unwrap_or_default(),
+                    forge: Some(label.to_string()),
+                    unpublished: false,
+                })
+            }),
+        ));
+    }
+
+    join_repo_tasks(tasks).await
+}
@@ -1,40 +1,90 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use eyre::Result;
 use log::{debug, info, warn};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-
-use crate::models::github::{Repository, RepositoryLanguages};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::guard::repo_key;
+use crate::models::github::{
+    Authorship, ContributorStats, IssueSearchItem, Repository, RepositoryLanguages,
+    RepositorySummary, SearchResults,
+};
+use crate::scraper::client::ApiClient;
+use crate::scraper::forge::{RepoData, ScrapeFuture, SourceForge, join_repo_tasks};
+use crate::scraper::graphql;
 use crate::utils::cache;
 use crate::utils::config::Config;
 
-#[derive(Clone, Debug)]
-pub struct GitHubRepoData {
-    pub name: String,
+/// A merged pull request to a repository the user does not own.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExternalContribution {
+    pub title: String,
+    /// Pull request page
     pub url: String,
-    pub stargazers_count: u64,
-    pub forks_count: u64,
-    pub size: u64,
-    pub importance_score: u64,
-    pub languages: Option<RepositoryLanguages>,
-    pub created_at: String,
-    pub pushed_at: String,
-    pub readme: Option<String>,
-    pub commits: u64,
+    /// `owner/name` of the upstream repository
+    pub repository: String,
+    pub repository_url: String,
+    pub stars: u64,
+    pub language: Option<String>,
+    /// Merge date (`YYYY-MM-DD`)
+    pub merged_at: String,
+}
+
+/// Relationship to the authenticated user, for `/user/repos` listings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Affiliation {
+    Owner,
+    Collaborator,
+    OrganizationMember,
 }
 
+impl Affiliation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Affiliation::Owner => "owner",
+            Affiliation::Collaborator => "collaborator",
+            Affiliation::OrganizationMember => "organization_member",
+        }
+    }
+}
+
+/// Whether links to private repositories are printed on the resume.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivateLinks {
+    /// Keep the project, but leave its link off the resume
+    #[default]
+    Redact,
+    Show,
+}
+
+/// Tries for contributor statistics that GitHub is still computing.
+const STATS_ATTEMPTS: u32 = 4;
+const STATS_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// The search API stops at 1000 results, 10 pages of 100.
+const SEARCH_MAX_PAGES: u32 = 10;
+
+/// Number of the last page in a `link` header.
+static LAST_PAGE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"[?&]page=(\d+)>; rel="last""#).expect("valid last page regex"));
+
 pub struct GitHubScraper {
     config: Config,
-    client: reqwest::Client,
+    client: ApiClient,
 }
 
 impl GitHubScraper {
     pub fn new(config: Config) -> Self {
         GitHubScraper {
+            client: ApiClient::github(&config.github),
             config,
-            client: reqwest::Client::new(),
         }
     }
 
@@ -52,27 +102,74 @@ impl GitHubScraper {
     }
 
     async fn list_repositories_internal(&self) -> Result<Vec<Repository>> {
+        let github = &self.config.github;
+
+        let mut repositories = match (&github.token, github.affiliation.is_empty()) {
+            (Some(_), false) => {
+                let affiliation = github
+                    .affiliation
+                    .iter()
+                    .map(Affiliation::as_str)
+                    .collect::<Vec<_>>()
+                    .join(",");
+                self.list_pages(&format!(
+                    "{}/user/repos?affiliation={}&visibility=all",
+                    github.api_url(),
+                    affiliation
+                ))
+                .await?
+            }
+            (None, false) => {
+                warn!("`affiliation` requires a GitHub token, listing public repositories only");
+                self.list_user_repositories().await?
+            }
+            (_, true) => self.list_user_repositories().await?,
+        };
+
+        for org in &github.orgs {
+            let org_repos = self
+                .list_pages(&format!("{}/orgs/{}/repos?type=all", github.api_url(), org))
+                .await?;
+            info!(
+                "found {} repositories in organization {}",
+                org_repos.len(),
+                org
+            );
+
+            for repo in org_repos {
+                if !repositories.iter().any(|r| r.id == repo.id) {
+                    repositories.push(repo);
+                }
+            }
+        }
+
+        Ok(repositories)
+    }
+
+    async fn list_user_repositories(&self) -> Result<Vec<Repository>> {
+        self.list_pages(&format!(
+            "{}/users/{}/repos",
+            self.config.github.api_url(),
+            self.config.github.username
+        ))
+        .await
+    }
+
+    /// Follows the pagination of a repository listing.
+    async fn list_pages(&self, url: &str) -> Result<Vec<Repository>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
         let mut page: u32 = 1;
         let mut repositories = Vec::new();
         loop {
-            let mut req = self
+            let response = self
                 .client
-                .get(format!(
-                    "https://api.github.com/users/{}/repos?per_page=100&page={}",
-                    self.config.github.username, page
-                ))
-                .header("User-Agent", "auto-resume-app");
-
-            if let Some(token) = &self.config.github.token {
-                req = req.header("Authorization", format!("token {}", token));
-            }
-
-            let response = req.send().await?;
+                .get(&format!("{}{}per_page=100&page={}", url, separator, page))
+                .await?
+                .error_for_status()?;
 
-            let has_next = response.headers().contains_key("link");
-            repositories.append(&mut response.json().await?);
+            repositories.append(&mut response.json()?);
 
-            match has_next {
+            match response.has_next_page() {
                 true => page += 1,
                 false => break,
             }
@@ -82,85 +179,179 @@ impl GitHubScraper {
     }
 
     pub async fn get_readme(&self, repo: &Repository) -> Result<Option<String>> {
-        let mut req = self
-            .client
-            .get(format!("{}/readme", repo.url))
-            .header("User-Agent", "auto-resume-app");
-        if let Some(token) = &self.config.github.token {
-            req = req.header("Authorization", format!("token {}", token));
+        let response = self.client.get(&format!("{}/readme", repo.url)).await?;
+        if response.status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
         }
 
-        let response = req.send().await?;
-
-        if response.status().is_success() {
-            let readme: serde_json::Value = response.json().await?;
-            if let Some(content) = readme.get("content") {
-                info!("Found README for repo: {}", repo.name);
-                debug!("Raw README content: {:?}", content);
-                let decoded = BASE64_STANDARD
-                    .decode(content.as_str().unwrap_or("").replace(['\n', '\r'], ""))?;
-                let readme_str = String::from_utf8(decoded)?;
-                return Ok(Some(readme_str));
-            }
+        let readme: serde_json::Value = response.error_for_status()?.json()?;
+        if let Some(content) = readme.get("content") {
+            info!("Found README for repo: {}", repo.name);
+            debug!("Raw README content: {:?}", content);
+            let decoded =
+                BASE64_STANDARD.decode(content.as_str().unwrap_or("").replace(['\n', '\r'], ""))?;
+            let readme_str = String::from_utf8_lossy(&decoded).into_owned();
+            return Ok(Some(readme_str));
         }
 
         Ok(None)
     }
 
+    /// Counts the default branch's commits from the last page number of a
+    /// one-commit-per-page listing.
     pub async fn get_commit_count(&self, repo: &Repository) -> Result<u64> {
-        let mut req = self
+        let response = self
             .client
-            .get(format!("{}/commits?per_page=1", repo.url))
-            .header("User-Agent", "auto-resume-app");
-        if let Some(token) = &self.config.github.token {
-            req = req.header("Authorization", format!("token {}", token));
+            .get(&format!("{}/commits?per_page=1", repo.url))
+            .await?;
+        // empty repository
+        if response.status == reqwest::StatusCode::CONFLICT {
+            return Ok(0);
         }
+        let response = response.error_for_status()?;
 
-        let response = req.send().await?;
-
-        let pattern = regex::Regex::new(
-            r#"<https://api\.github\.com/repositories/\d+/commits\?per_page=1&page=2>; rel="next", <https://api\.github\.com/repositories/\d+/commits\?per_page=1&page=(\d+)>; rel="last""#,
-        )?;
-        if let Some(link_header) = response.headers().get("link")
-            && let Some(captures) = pattern.captures(link_header.to_str()?)
-            && let Some(last_page_match) = captures.get(1)
-            && let Ok(last_page) = last_page_match.as_str().parse::<u64>()
+        if let Some(link_header) = response.header("link")
+            && let Some(captures) = LAST_PAGE.captures(link_header)
+            && let Ok(last_page) = captures[1].parse::<u64>()
         {
             return Ok(last_page);
         }
 
-        warn!("Could not determine commit count for repo: {}", repo.name);
+        // a single page: zero or one commit
+        let commits: Vec<serde_json::Value> = response.json()?;
+        Ok(commits.len() as u64)
+    }
 
-        Ok(0)
+    /// Fetches the user's share of the repository's commits and lines from
+    /// the contributor statistics, which GitHub computes on first request
+    /// (answering `202 Accepted` until they are ready).
+    pub async fn get_authorship(&self, name: &str, url: &str) -> Result<Option<Authorship>> {
+        for attempt in 1..=STATS_ATTEMPTS {
+            let response = self
+                .client
+                .get(&format!("{}/stats/contributors", url))
+                .await?;
+
+            match response.status {
+                reqwest::StatusCode::ACCEPTED => {
+                    debug!(
+                        "contributor statistics for {} are being computed (attempt {}/{})",
+                        name, attempt, STATS_ATTEMPTS
+                    );
+                    tokio::time::sleep(STATS_RETRY_DELAY).await;
+                }
+                reqwest::StatusCode::NO_CONTENT => return Ok(None),
+                // too many commits for GitHub to compute statistics
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                    warn!("GitHub has no contributor statistics for {}", name);
+                    return Ok(None);
+                }
+                _ => {
+                    let stats: Vec<ContributorStats> = response.error_for_status()?.json()?;
+                    return Ok(Some(Authorship::from_stats(
+                        &stats,
+                        &self.config.github.username,
+                    )));
+                }
+            }
+        }
+
+        warn!("contributor statistics for {} were not ready in time", name);
+        Ok(None)
     }
 
-    pub async fn get_languages(&self, repo: &Repository) -> Result<RepositoryLanguages> {
-        let mut req = self
-            .client
-            .get(format!("{}/languages", repo.url))
-            .header("User-Agent", "auto-resume-app");
-        if let Some(token) = &self.config.github.token {
-            req = req.header("Authorization", format!("token {}", token));
+    /// Searches the user's merged pull requests outside their own
+    /// repositories and the configured organizations.
+    pub async fn search_merged_pull_requests(&self) -> Result<Vec<IssueSearchItem>> {
+        let github = &self.config.github;
+        let mut query = format!(
+            "is:pr is:merged author:{} -user:{}",
+            github.username, github.username
+        );
+        for org in &github.orgs {
+            query.push_str(&format!(" -org:{}", org));
         }
+        debug!("searching pull requests: {}", query);
 
-        let response = req.send().await?;
+        let mut pull_requests = Vec::new();
+        for page in 1..=SEARCH_MAX_PAGES {
+            let response = self
+                .client
+                .get(&format!(
+                    "{}/search/issues?q={}&per_page=100&page={}",
+                    github.api_url(),
+                    // usernames and organization names need no escaping
+                    query.replace(' ', "+"),
+                    page
+                ))
+                .await?
+                .error_for_status()?;
+
+            let mut results: SearchResults<IssueSearchItem> = response.json()?;
+            if page == 1 {
+                info!("found {} merged pull requests", results.total_count);
+            }
+            pull_requests.append(&mut results.items);
 
-        let languages: RepositoryLanguages = response.json().await?;
+            if !response.has_next_page() {
+                break;
+            }
+        }
 
-        Ok(languages)
+        Ok(pull_requests)
     }
+
+    /// Fetches a repository the user may not own, from its API URL. `None`
+    /// when it no longer exists.
+    pub async fn get_repository_summary(&self, url: &str) -> Result<Option<RepositorySummary>> {
+        let response = self.client.get(url).await?;
+        if response.status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        Ok(Some(response.error_for_status()?.json()?))
+    }
+
+    pub async fn get_languages(&self, repo: &Repository) -> Result<RepositoryLanguages> {
+        self.client
+            .get(&format!("{}/languages", repo.url))
+            .await?
+            .error_for_status()?
+            .json()
+    }
+}
+
+/// github.com, or the GitHub Enterprise Server at `api_url`.
+pub struct GitHubForge {
+    config: Config,
 }
 
-pub async fn scrape_github_profile(config: &Config) -> Result<Vec<GitHubRepoData>> {
-    info!("scraping GitHub profile");
+impl GitHubForge {
+    pub fn new(config: Config) -> Self {
+        GitHubForge { config }
+    }
+}
+
+impl SourceForge for GitHubForge {
+    fn name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn scrape(&self) -> ScrapeFuture<'_> {
+        Box::pin(scrape_repositories(&self.config))
+    }
+}
 
-    cache::init_cache()?;
+async fn scrape_repositories(config: &Config) -> Result<Vec<RepoData>> {
+    if config.github.token.is_some() && config.github.graphql {
+        return graphql::scrape_repositories(config).await;
+    }
 
     let scraper = Arc::new(GitHubScraper::new(config.clone()));
     let repos = scraper.list_repositories().await?;
 
     if repos.is_empty() {
-        info!("no public repositories found on GitHub profile");
+        info!("no repositories found on GitHub profile");
         return Ok(Vec::new());
     }
 
@@ -173,47 +364,45 @@ pub async fn scrape_github_profile(config: &Config) -> Result<Vec<GitHubRepoData
     let mut tasks = Vec::new();
     for repo in repos.into_iter() {
         let scraper_clone = Arc::clone(&scraper);
+        let name = repo.name.clone();
         let task = tokio::spawn(async move {
             // Check cache first for README
-            let readme = if let Some(cached) = cache::get_cached_readme(&repo.name) {
-                cached
-            } else {
-                match scraper_clone.get_readme(&repo).await {
-                    Ok(Some(content)) => {
-                        let _ = cache::cache_readme(&repo.name, &content);
-                        content
+            // keyed by owner too, as org repositories can share a name
+            let cache_key = repo_key(&repo.url);
+            let readme = match cache::get_cached_readme(&cache_key) {
+                Some(cached) => Some(cached),
+                None => {
+                    let readme = scraper_clone.get_readme(&repo).await?;
+                    if let Some(content) = &readme {
+                        let _ = cache::cache_readme(&cache_key, content);
                     }
-                    _ => String::new(),
+                    readme
                 }
             };
 
-            let commits = scraper_clone.get_commit_count(&repo).await.unwrap_or(0);
+            let commits = scraper_clone.get_commit_count(&repo).await?;
+
+            let languages = scraper_clone.get_languages(&repo).await?;
 
-            let languages = scraper_clone.get_languages(&repo).await.ok();
+            let authorship = scraper_clone.get_authorship(&repo.name, &repo.url).await?;
 
-            (
+            Ok::<_, eyre::Report>((
                 repo,
-                if readme.is_empty() {
-                    None
-                } else {
-                    Some(readme)
-                },
+                readme.filter(|readme| !readme.is_empty()),
                 commits,
-                languages,
-            )
+                Some(languages),
+                authorship,
+            ))
         });
-        tasks.push(task);
+        tasks.push((name, task));
     }
 
     // Await all tasks and collect results in parallel using rayon
-    let mut completed_tasks = Vec::with_capacity(tasks.len());
-    for task in tasks {
-        completed_tasks.push(task.await?);
-    }
+    let completed_tasks = join_repo_tasks(tasks).await?;
 
-    let result: Vec<GitHubRepoData> = completed_tasks
+    let result: Vec<RepoData> = completed_tasks
         .into_par_iter()
-        .map(|(repo, readme, commits, languages)| GitHubRepoData {
+        .map(|(repo, readme, commits, languages, authorship)| RepoData {
             name: repo.name.clone(),
             url: repo.url.clone(),
             stargazers_count: repo.stargazers_count,
@@ -225,9 +414,83 @@ pub async fn scrape_github_profile(config: &Config) -> Result<Vec<GitHubRepoData
             pushed_at: repo.pushed_at.clone(),
             readme,
             commits,
+            private: repo.private,
+            authorship,
+            topics: repo.topics.clone(),
+            forge: Some(config.github.label().to_string()),
+            unpublished: false,
         })
         .collect();
 
-    info!("found {} repositories to analyze", result.len());
     Ok(result)
 }
+
+/// Collects the user's merged pull requests to public repositories owned by
+/// someone else, most starred first.
+pub async fn scrape_contributions(config: &Config) -> Result<Vec<ExternalContribution>> {
+    if !config.github.contributions || config.github.username.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    info!("searching merged pull requests to other projects");
+
+    let scraper = GitHubScraper::new(config.clone());
+    let pull_requests = scraper.search_merged_pull_requests().await?;
+
+    let mut repositories: HashMap<String, Option<RepositorySummary>> = HashMap::new();
+    let mut contributions = Vec::new();
+    for pull_request in pull_requests {
+        let Some(merged_at) = pull_request
+            .pull_request
+            .and_then(|pr| pr.merged_at)
+            .or(pull_request.closed_at)
+        else {
+            continue;
+        };
+
+        if !repositories.contains_key(&pull_request.repository_url) {
+            let summary = scraper
+                .get_repository_summary(&pull_request.repository_url)
+                .await?;
+            repositories.insert(pull_request.repository_url.clone(), summary);
+        }
+
+        let Some(repo) = &repositories[&pull_request.repository_url] else {
+            continue;
+        };
+        if repo.private {
+            debug!(
+                "skipping pull request to private repository {}",
+                repo.full_name
+            );
+            continue;
+        }
+
+        contributions.push(ExternalContribution {
+            title: pull_request.title,
+            url: pull_request.html_url,
+            repository: repo.full_name.clone(),
+            repository_url: repo.html_url.clone(),
+            stars: repo.stargazers_count,
+            language: repo.language.clone(),
+            merged_at: merged_at.get(..10).unwrap_or(&merged_at).to_string(),
+        });
+    }
+
+    contributions.sort_by(|a, b| {
+        b.stars
+            .cmp(&a.stars)
+            .then_with(|| b.merged_at.cmp(&a.merged_at))
+    });
+
+    info!(
+        "found {} merged pull requests to {} other repositories",
+        contributions.len(),
+        repositories
+            .values()
+            .flatten()
+            .filter(|repo| !repo.private)
+            .count()
+    );
+    Ok(contributions)
+}
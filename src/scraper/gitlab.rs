@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use eyre::Result;
+use log::{debug, info};
+
+use crate::models::github::{RepositoryLanguages, importance_score};
+use crate::models::gitlab::Project;
+use crate::scraper::client::ApiClient;
+use crate::scraper::forge::{RepoData, ScrapeFuture, SourceForge, join_repo_tasks};
+use crate::utils::config::ForgeConfig;
+
+pub struct GitLabScraper {
+    /// `{url}/api/v4`
+    api_url: String,
+    username: String,
+    client: ApiClient,
+}
+
+impl GitLabScraper {
+    pub fn new(forge: &ForgeConfig) -> Self {
+        GitLabScraper {
+            api_url: format!("{}/api/v4", forge.url()),
+            username: forge.username.clone(),
+            client: ApiClient::new(
+                "GitLab",
+                forge
+                    .token
+                    .as_ref()
+                    .map(|token| format!("Bearer {}", token)),
+            ),
+        }
+    }
+
+    /// Lists the user's own projects, forks excluded.
+    pub async fn list_projects(&self) -> Result<Vec<Project>> {
+        let mut page: u32 = 1;
+        let mut projects: Vec<Project> = Vec::new();
+        loop {
+            let response = self
+                .client
+                .get(&format!(
+                    "{}/users/{}/projects?statistics=true&per_page=100&page={}",
+                    self.api_url, self.username, page
+                ))
+                .await?
+                .error_for_status()?;
+
+            projects.append(&mut response.json()?);
+
+            match response.has_next_page() {
+                true => page += 1,
+                false => break,
+            }
+        }
+
+        projects.retain(|project| project.forked_from_project.is_none());
+        Ok(projects)
+    }
+
+    pub async fn get_readme(&self, project: &Project) -> Result<Option<String>> {
+        let (Some(path), Some(branch)) = (project.readme_path(), &project.default_branch) else {
+            return Ok(None);
+        };
+
+        let response = self
+            .client
+            .get(&format!(
+                "{}/projects/{}/repository/files/{}/raw?ref={}",
+                self.api_url,
+                project.id,
+                path.replace('/', "%2F"),
+                branch
+            ))
+            .await?;
+        if response.status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        info!("Found README for repo: {}", project.name);
+        Ok(Some(response.error_for_status()?.body))
+    }
+
+    /// Counts the default branch's commits, from the project statistics when
+    /// the token can read them.
+    pub async fn get_commit_count(&self, project: &Project) -> Result<u64> {
+        if let Some(statistics) = &project.statistics {
+            return Ok(statistics.commit_count);
+        }
+        if project.empty_repo {
+            return Ok(0);
+        }
+
+        let response = self
+            .client
+            .get(&format!(
+                "{}/projects/{}/repository/commits?per_page=1",
+                self.api_url, project.id
+            ))
+            .await?
+            .error_for_status()?;
+
+        // GitLab leaves out the total above 10,000 commits
+        Ok(response
+            .header("x-total")
+            .and_then(|total| total.parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// GitLab reports languages as percentages, turned into bytes of the
+    /// repository size when the statistics are available.
+    pub async fn get_languages(&self, project: &Project) -> Result<RepositoryLanguages> {
+        let percentages: HashMap<String, f64> = self
+            .client
+            .get(&format!(
+                "{}/projects/{}/languages",
+                self.api_url, project.id
+            ))
+            .await?
+            .error_for_status()?
+            .json()?;
+
+        let total = project
+            .statistics
+            .as_ref()
+            .map(|statistics| statistics.repository_size)
+            .filter(|size| *size > 0)
+            .unwrap_or(10_000);
+        let languages: Vec<(String, u64)> = percentages
+            .into_iter()
+            .map(|(language, percentage)| {
+                let bytes = (percentage / 100.0 * total as f64).round() as u64;
+                (language, bytes)
+            })
+            .collect();
+
+        Ok(RepositoryLanguages {
+            total_byte_count: languages.iter().map(|(_, bytes)| bytes).sum(),
+            languages,
+        })
+    }
+}
+
+/// A gitlab.com or self-hosted GitLab account.
+pub struct GitLabForge {
+    config: ForgeConfig,
+}
+
+impl GitLabForge {
+    pub fn new(config: ForgeConfig) -> Self {
+        GitLabForge { config }
+    }
+}
+
+impl SourceForge for GitLabForge {
+    fn name(&self) -> &str {
+        self.config.host()
+    }
+
+    fn scrape(&self) -> ScrapeFuture<'_> {
+        Box::pin(scrape_repositories(&self.config))
+    }
+}
+
+async fn scrape_repositories(forge: &ForgeConfig) -> Result<Vec<RepoData>> {
+    let scraper = Arc::new(GitLabScraper::new(forge));
+    let label = forge.label();
+    let projects = scraper.list_projects().await?;
+
+    debug!(
+        "fetching README and commit data for {} GitLab projects (parallel)",
+        projects.len()
+    );
+
+    let mut tasks = Vec::new();
+    for project in projects {
+        let scraper = Arc::clone(&scraper);
+        let name = project.name.clone();
+        tasks.push((
+            name,
+            tokio::spawn(async move {
+                let readme = scraper.get_readme(&project).await?;
+                let commits = scraper.get_commit_count(&project).await?;
+                let languages = scraper.get_languages(&project).await?;
+
+                let size = project
+                    .statistics
+                    .as_ref()
+                    .map(|statistics| statistics.repository_size / 1024)
+                    .unwrap_or(0);
+                Ok::<_, eyre::Report>(RepoData {
+                    name: project.name.clone(),
+                    url: project.web_url.clone(),
+                    stargazers_count: project.star_count,
+                    forks_count: project.forks_count,
+                    size,
+                    importance_score: importance_score(
+                        project.star_count,
+                        project.forks_count,
+                        size,
+                    ),
+                    languages: Some(languages),
+                    created_at: project.created_at.clone(),
+                    pushed_at: project.last_activity_at.clone(),
+                    readme: readme.filter(|readme| !readme.is_empty()),
+                    commits,
+                    private: project.is_private(),
+                    authorship: None,
+                    topics: project.topics,
+                    forge: Some(label.to_string()),
+                    unpublished: false,
+                })
+            }),
+        ));
+    }
+
+    join_repo_tasks(tasks).await
+}
@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use eyre::{Result, eyre};
+use log::{debug, info, warn};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::models::github::{RepositoryLanguages, importance_score};
+use crate::models::graphql::{GraphQLResponse, OwnerData, RepositoryNode};
+use crate::scraper::client::ApiClient;
+use crate::scraper::forge::{RepoData, join_repo_tasks};
+use crate::scraper::github::GitHubScraper;
+use crate::utils::config::Config;
+
+/// Repositories per page, kept low since every node carries its README.
+const PAGE_SIZE: u32 = 50;
+
+/// Everything `RepoData` needs, so a page of repositories costs a
+/// single request instead of three per repository.
+const REPOSITORY_PAGE: &str = r#"
+fragment RepositoryPage on RepositoryConnection {
+  pageInfo { hasNextPage endCursor }
+  nodes {
+    databaseId
+    name
+    nameWithOwner
+    isPrivate
+    stargazerCount
+    forkCount
+    diskUsage
+    createdAt
+    pushedAt
+    languages(first: 20, orderBy: {field: SIZE, direction: DESC}) {
+      totalSize
+      edges { size node { name } }
+    }
+    repositoryTopics(first: 20) { nodes { topic { name } } }
+    defaultBranchRef { target { ... on Commit { history { totalCount } } } }
+    readmeMd: object(expression: "HEAD:README.md") { ... on Blob { text } }
+    readmeLower: object(expression: "HEAD:readme.md") { ... on Blob { text } }
+    readmeRst: object(expression: "HEAD:README.rst") { ... on Blob { text } }
+    readmeTxt: object(expression: "HEAD:README.txt") { ... on Blob { text } }
+    readme: object(expression: "HEAD:README") { ... on Blob { text } }
+  }
+}
+"#;
+
+/// Repositories of the authenticated user, like `/user/repos?affiliation=...`
+const VIEWER_QUERY: &str = r#"
+query($first: Int!, $cursor: String, $affiliations: [RepositoryAffiliation]) {
+  owner: viewer {
+    repositories(first: $first, after: $cursor, isFork: false,
+                 affiliations: $affiliations, ownerAffiliations: $affiliations) {
+      ...RepositoryPage
+    }
+  }
+}
+"#;
+
+/// Public repositories owned by a user, like `/users/{user}/repos`
+const USER_QUERY: &str = r#"
+query($first: Int!, $cursor: String, $login: String!) {
+  owner: user(login: $login) {
+    repositories(first: $first, after: $cursor, isFork: false,
+                 ownerAffiliations: [OWNER], privacy: PUBLIC) {
+      ...RepositoryPage
+    }
+  }
+}
+"#;
+
+/// Every repository of an organization visible to the token, like `/orgs/{org}/repos`
+const ORGANIZATION_QUERY: &str = r#"
+query($first: Int!, $cursor: String, $login: String!) {
+  owner: organization(login: $login) {
+    repositories(first: $first, after: $cursor, isFork: false) {
+      ...RepositoryPage
+    }
+  }
+}
+"#;
+
+/// Lists repositories through the GraphQL API, which requires a token.
+pub struct GitHubGraphQLScraper {
+    config: Config,
+    client: ApiClient,
+}
+
+impl GitHubGraphQLScraper {
+    pub fn new(config: Config) -> Self {
+        GitHubGraphQLScraper {
+            client: ApiClient::github(&config.github),
+            config,
+        }
+    }
+
+    /// Lists the same repositories as the REST scraper: the user's (or, with
+    /// `affiliation`, the authenticated user's) and the configured
+    /// organizations', forks excluded, most important first.
+    pub async fn list_repositories(&self) -> Result<Vec<RepositoryNode>> {
+        let github = &self.config.github;
+
+        let mut repositories = if github.affiliation.is_empty() {
+            self.list_pages(USER_QUERY, json!({ "login": github.username }))
+                .await?
+        } else {
+            let affiliations: Vec<String> = github
+                .affiliation
+                .iter()
+                .map(|affiliation| affiliation.as_str().to_uppercase())
+                .collect();
+            self.list_pages(VIEWER_QUERY, json!({ "affiliations": affiliations }))
+                .await?
+        };
+
+        for org in &github.orgs {
+            let org_repos = self
+                .list_pages(ORGANIZATION_QUERY, json!({ "login": org }))
+                .await?;
+            info!(
+                "found {} repositories in organization {}",
+                org_repos.len(),
+                org
+            );
+
+            for repo in org_repos {
+                if !repositories
+                    .iter()
+                    .any(|r| r.database_id == repo.database_id)
+                {
+                    repositories.push(repo);
+                }
+            }
+        }
+
+        repositories.sort_by_key(|repo| {
+            std::cmp::Reverse(importance_score(
+                repo.stargazer_count,
+                repo.fork_count,
+                repo.disk_usage.unwrap_or(0),
+            ))
+        });
+        Ok(repositories)
+    }
+
+    /// Follows the cursor of a repository listing query.
+    async fn list_pages(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<Vec<RepositoryNode>> {
+        let mut repositories = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut variables = variables.clone();
+            variables["first"] = json!(PAGE_SIZE);
+            variables["cursor"] = json!(cursor);
+
+            let data: OwnerData = self.query(query, variables).await?;
+            let connection = data
+                .owner
+                .ok_or_else(|| eyre!("GitHub GraphQL API returned no repository owner"))?
+                .repositories;
+            debug!(
+                "fetched {} repositories through GraphQL",
+                connection.nodes.len()
+            );
+            repositories.extend(connection.nodes);
+
+            match connection.page_info.end_cursor {
+                Some(end_cursor) if connection.page_info.has_next_page => cursor = Some(end_cursor),
+                _ => break,
+            }
+        }
+
+        Ok(repositories)
+    }
+
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T> {
+        if self.config.github.token.is_none() {
+            return Err(eyre!("the GitHub GraphQL API requires a token"));
+        }
+
+        let response: GraphQLResponse<T> = self
+            .client
+            .post(
+                &self.config.github.graphql_url(),
+                &json!({
+                    "query": format!("{}{}", query, REPOSITORY_PAGE),
+                    "variables": variables,
+                }),
+            )
+            .await?
+            .error_for_status()?
+            .json()?;
+        let messages: Vec<String> = response
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect();
+
+        match response.data {
+            Some(data) => {
+                for message in &messages {
+                    warn!("GitHub GraphQL API: {}", message);
+                }
+                Ok(data)
+            }
+            None => Err(eyre!("GitHub GraphQL API error: {}", messages.join("; "))),
+        }
+    }
+}
+
+/// Builds the repository data from a GraphQL node, in the same shape as the
+/// REST scrape (API URL included).
+fn repo_data(node: RepositoryNode, api_url: &str, forge: &str) -> RepoData {
+    let size = node.disk_usage.unwrap_or(0);
+    let readme = [
+        node.readme_md,
+        node.readme_lower,
+        node.readme_rst,
+        node.readme_txt,
+        node.readme,
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|blob| blob.text)
+    .filter(|text| !text.is_empty());
+
+    RepoData {
+        url: format!("{}/repos/{}", api_url, node.name_with_owner),
+        name: node.name,
+        stargazers_count: node.stargazer_count,
+        forks_count: node.fork_count,
+        size,
+        importance_score: importance_score(node.stargazer_count, node.fork_count, size),
+        languages: node.languages.map(|languages| RepositoryLanguages {
+            languages: languages
+                .edges
+                .into_iter()
+                .map(|edge| (edge.node.name, edge.size))
+                .collect(),
+            total_byte_count: languages.total_size,
+        }),
+        pushed_at: node.pushed_at.unwrap_or_else(|| node.created_at.clone()),
+        created_at: node.created_at,
+        readme,
+        commits: node
+            .default_branch_ref
+            .and_then(|branch| branch.target)
+            .and_then(|commit| commit.history)
+            .map(|history| history.total_count)
+            .unwrap_or(0),
+        private: node.is_private,
+        authorship: None,
+        topics: node
+            .repository_topics
+            .nodes
+            .into_iter()
+            .map(|topic| topic.topic.name)
+            .collect(),
+        forge: Some(forge.to_string()),
+        unpublished: false,
+    }
+}
+
+/// Scrapes the profile with paginated GraphQL queries. Contributor
+/// statistics have no GraphQL equivalent and are still fetched per repository.
+pub async fn scrape_repositories(config: &Config) -> Result<Vec<RepoData>> {
+    let repos = GitHubGraphQLScraper::new(config.clone())
+        .list_repositories()
+        .await?;
+
+    if repos.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    info!(
+        "fetching contributor statistics for {} repositories (parallel)",
+        repos.len()
+    );
+
+    let rest = Arc::new(GitHubScraper::new(config.clone()));
+    let forge = config.github.label();
+    let mut tasks = Vec::new();
+    for node in repos {
+        let rest = Arc::clone(&rest);
+        let api_url = config.github.api_url().to_string();
+        tasks.push((
+            node.name.clone(),
+            tokio::spawn(async move {
+                let mut repo = repo_data(node, &api_url, forge);
+                repo.authorship = rest.get_authorship(&repo.name, &repo.url).await?;
+                Ok::<_, eyre::Report>(repo)
+            }),
+        ));
+    }
+
+    join_repo_tasks(tasks).await
+}
@@ -2,10 +2,11 @@ use std::path::Path;
 
 use eyre::Result;
 use log::{info, warn};
+use serde::{Deserialize, Serialize};
 
 use crate::utils::cli::Args;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct JobDescription {
     pub title: String,
@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use eyre::{Result, eyre};
+use log::{debug, info, warn};
+use tokio::process::Command;
+
+use crate::models::github::{Authorship, RepositoryLanguages, importance_score};
+use crate::scraper::forge::{RepoData, ScrapeFuture, SourceForge, host_label};
+use crate::utils::config::{Config, LocalRepoConfig};
+
+/// Languages by file extension (or, for files without one, by file name),
+/// named like GitHub's so languages line up across hosts.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("go", "Go"),
+    ("py", "Python"),
+    ("js", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("cjs", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("scala", "Scala"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("cxx", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("fs", "F#"),
+    ("swift", "Swift"),
+    ("m", "Objective-C"),
+    ("rb", "Ruby"),
+    ("php", "PHP"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("erl", "Erlang"),
+    ("hs", "Haskell"),
+    ("ml", "OCaml"),
+    ("clj", "Clojure"),
+    ("dart", "Dart"),
+    ("lua", "Lua"),
+    ("zig", "Zig"),
+    ("nim", "Nim"),
+    ("jl", "Julia"),
+    ("r", "R"),
+    ("sh", "Shell"),
+    ("bash", "Shell"),
+    ("zsh", "Shell"),
+    ("ps1", "PowerShell"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("css", "CSS"),
+    ("scss", "SCSS"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("nix", "Nix"),
+    ("tf", "HCL"),
+    ("tex", "TeX"),
+    ("sol", "Solidity"),
+    ("Dockerfile", "Dockerfile"),
+    ("Makefile", "Makefile"),
+];
+
+/// Repositories cloned on this machine, analyzed from their Git history
+/// with the `git` command, so nothing is fetched from a forge.
+pub struct LocalForge {
+    config: Config,
+}
+
+impl LocalForge {
+    pub fn new(config: Config) -> Self {
+        LocalForge { config }
+    }
+}
+
+impl SourceForge for LocalForge {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn scrape(&self) -> ScrapeFuture<'_> {
+        Box::pin(scrape_repositories(&self.config))
+    }
+}
+
+async fn scrape_repositories(config: &Config) -> Result<Vec<RepoData>> {
+    let mut repos = Vec::with_capacity(config.local_repos.len());
+    for local in &config.local_repos {
+        if let Some(repo) = analyze_repository(local, config.resume.email.as_deref()).await? {
+            repos.push(repo);
+        }
+    }
+
+    Ok(repos)
+}
+
+/// The configured name of a local repository, or its directory's.
+pub fn repo_name(local: &LocalRepoConfig) -> String {
+    local.name.clone().unwrap_or_else(|| {
+        local
+            .path
+            .canonicalize()
+            .ok()
+            .and_then(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| local.path.display().to_string())
+    })
+}
+
+/// Builds the repository data from the default branch's history and tree.
+/// `None` for repositories without commits.
+async fn analyze_repository(
+    local: &LocalRepoConfig,
+    resume_email: Option<&str>,
+) -> Result<Option<RepoData>> {
+    let path = local.path.as_path();
+    git(path, &["rev-parse", "--git-dir"])
+        .await
+        .map_err(|_| eyre!("not a Git repository: {}", path.display()))?;
+    if git(path, &["rev-parse", "--verify", "--quiet", "HEAD"])
+        .await
+        .is_err()
+    {
+        warn!("skipping {}, which has no commits", path.display());
+        return Ok(None);
+    }
+
+    let name = repo_name(local);
+    info!("analyzing local repository {}", name);
+
+    let mut emails = local.emails.clone();
+    if emails.is_empty() {
+        if let Ok(email) = git(path, &["config", "user.email"]).await {
+            emails.push(email.trim().to_string());
+        }
+        emails.extend(resume_email.map(str::to_string));
+    }
+
+    let log = git(
+        path,
+        &["log", "--format=%x1e%ae%x1f%aI", "--numstat", "HEAD"],
+    )
+    .await?;
+    let history = History::from_log(&log, &emails);
+    debug!(
+        "commits by author in {}: {:?}",
+        name, history.commits_by_email
+    );
+
+    let tree = git(path, &["ls-tree", "-r", "-l", "-z", "--full-tree", "HEAD"]).await?;
+    let files = tree_files(&tree);
+    let languages = languages(&files);
+    let size = files.iter().map(|(_, bytes)| bytes).sum::<u64>() / 1024;
+
+    let readme = match files
+        .iter()
+        .map(|(file, _)| *file)
+        .find(|file| !file.contains('/') && file.to_lowercase().starts_with("readme"))
+    {
+        Some(file) => Some(git(path, &["show", &format!("HEAD:{}", file)]).await?),
+        None => None,
+    };
+
+    Ok(Some(RepoData {
+        // the path stays out of prompts
+        url: local.url.clone().unwrap_or_else(|| name.clone()),
+        name,
+        stargazers_count: 0,
+        forks_count: 0,
+        size,
+        importance_score: importance_score(0, 0, size),
+        languages: Some(languages),
+        created_at: history.first_commit.clone().unwrap_or_default(),
+        pushed_at: history.last_commit.clone().unwrap_or_default(),
+        readme: readme.filter(|readme| !readme.is_empty()),
+        commits: history.authorship.total_commits,
+        // never pushed anywhere, so there is no page to link to
+        private: local.url.is_none(),
+        authorship: Some(history.authorship),
+        topics: Vec::new(),
+        forge: local.url.as_deref().map(host_label),
+        unpublished: local.url.is_none(),
+    }))
+}
+
+/// What `git log --format=%x1e%ae%x1f%aI --numstat` tells about a repository.
+struct History {
+    commits_by_email: HashMap<String, u64>,
+    authorship: Authorship,
+    first_commit: Option<String>,
+    last_commit: Option<String>,
+}
+
+impl History {
+    /// Parses the log, crediting commits authored with any of `emails`
+    /// (case-insensitive) to the user.
+    fn from_log(log: &str, emails: &[String]) -> Self {
+        let mut commits_by_email: HashMap<String, u64> = HashMap::new();
+        let mut authorship = Authorship {
+            commits: 0,
+            total_commits: 0,
+            additions: 0,
+            deletions: 0,
+            first_contribution: None,
+            last_contribution: None,
+        };
+        let mut dates: Vec<DateTime<Utc>> = Vec::new();
+        let mut own_dates: Vec<DateTime<Utc>> = Vec::new();
+
+        for record in log.split('\x1e').filter(|record| !record.trim().is_empty()) {
+            let mut lines = record.lines();
+            let Some((email, date)) = lines.next().and_then(|header| header.split_once('\x1f'))
+            else {
+                continue;
+            };
+            let email = email.to_lowercase();
+            let own = emails.iter().any(|e| e.eq_ignore_ascii_case(&email));
+            let date = DateTime::parse_from_rfc3339(date.trim())
+                .ok()
+                .map(|date| date.with_timezone(&Utc));
+
+            authorship.total_commits += 1;
+            *commits_by_email.entry(email).or_default() += 1;
+            dates.extend(date);
+            if !own {
+                continue;
+            }
+
+            authorship.commits += 1;
+            own_dates.extend(date);
+            // `added\tdeleted\tpath`, `-` for binary files
+            for line in lines {
+                let mut columns = line.split('\t');
+                if let (Some(added), Some(deleted), Some(_)) =
+                    (columns.next(), columns.next(), columns.next())
+                {
+                    authorship.additions += added.parse::<u64>().unwrap_or(0);
+                    authorship.deletions += deleted.parse::<u64>().unwrap_or(0);
+                }
+            }
+        }
+
+        let day = |date: &DateTime<Utc>| date.date_naive().to_string();
+        authorship.first_contribution = own_dates.iter().min().map(day);
+        authorship.last_contribution = own_dates.iter().max().map(day);
+        let timestamp = |date: &DateTime<Utc>| date.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        History {
+            commits_by_email,
+            authorship,
+            first_commit: dates.iter().min().map(timestamp),
+            last_commit: dates.iter().max().map(timestamp),
+        }
+    }
+}
+
+/// Paths and sizes of the files in `git ls-tree -r -l -z` output, whose
+/// NUL-terminated entries look like `<mode> blob <hash> <size>\t<path>`.
+fn tree_files(tree: &str) -> Vec<(&str, u64)> {
+    tree.split('\0')
+        .filter_map(|line| {
+            let (meta, path) = line.split_once('\t')?;
+            let mut fields = meta.split_whitespace();
+            if fields.nth(1)? != "blob" {
+                return None;
+            }
+            let size = fields.nth(1)?.parse().ok()?;
+            Some((path, size))
+        })
+        .collect()
+}
+
+/// Bytes per language of the files with a known extension.
+fn languages(files: &[(&str, u64)]) -> RepositoryLanguages {
+    let mut bytes: HashMap<&str, u64> = HashMap::new();
+    for (path, size) in files {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let key = match file_name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => extension,
+            _ => file_name,
+        };
+        if let Some((_, language)) = LANGUAGES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(key))
+        {
+            *bytes.entry(language).or_default() += size;
+        }
+    }
+
+    let mut languages: Vec<(String, u64)> = bytes
+        .into_iter()
+        .map(|(language, size)| (language.to_string(), size))
+        .collect();
+    languages.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    RepositoryLanguages {
+        total_byte_count: languages.iter().map(|(_, size)| size).sum(),
+        languages,
+    }
+}
+
+/// Runs `git -C <path> <args>` and returns its output.
+pub async fn git(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| eyre!("failed to run git (is it installed?): {}", e))?;
+
+    if !output.status.success() {
+        return Err(eyre!(
+            "git {} failed in {}: {}",
+            args.join(" "),
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -1,2 +1,8 @@
+pub mod client;
+pub mod forge;
+pub mod gitea;
 pub mod github;
+pub mod gitlab;
+pub mod graphql;
 pub mod job;
+pub mod local;
@@ -0,0 +1,415 @@
+use eyre::{Result, eyre};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::chat::agent::{GenerationInput, LLMResumeOutput, ResumeAgent};
+use crate::chat::section::{OutputSection, OutputTarget};
+use crate::pipeline::resume::apply_resume_output;
+use crate::render::renderer::ResumeRenderer;
+use crate::render::text::TextResumeRenderer;
+use crate::utils::config::Config;
+
+const HELP: &str = " ↑↓ select · e edit · d delete · J/K reorder · r regenerate · f feedback · PgUp/PgDn scroll · q done ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Section(OutputSection),
+    Entry(OutputSection, usize),
+    /// A bullet point, or a single skill of a category
+    Bullet(OutputSection, usize, usize),
+}
+
+/// What the text input is for.
+#[derive(Debug, Clone, Copy)]
+enum Prompt {
+    /// Replaces the row's text
+    Edit(Row),
+    /// Regenerates the target following the entered feedback
+    Feedback(OutputTarget),
+}
+
+/// Single-line text input, edited in place.
+struct Input {
+    prompt: Prompt,
+    text: Vec<char>,
+    cursor: usize,
+}
+
+/// Full-screen review of the generated content: entries can be edited,
+/// deleted, reordered or regenerated with feedback, with a live preview of
+/// the resulting resume.
+pub struct ReviewScreen<'a> {
+    agent: &'a ResumeAgent,
+    input: GenerationInput<'a>,
+    config: &'a Config,
+    output: LLMResumeOutput,
+    rows: Vec<Row>,
+    list: ListState,
+    editing: Option<Input>,
+    preview: String,
+    scroll: u16,
+    status: String,
+}
+
+impl<'a> ReviewScreen<'a> {
+    /// `config` is the configured resume, before the LLM output is merged in.
+    pub fn new(
+        agent: &'a ResumeAgent,
+        input: GenerationInput<'a>,
+        config: &'a Config,
+        output: LLMResumeOutput,
+    ) -> Self {
+        let mut screen = Self {
+            agent,
+            input,
+            config,
+            output,
+            rows: Vec::new(),
+            list: ListState::default().with_selected(Some(0)),
+            editing: None,
+            preview: String::new(),
+            scroll: 0,
+            status: String::from("review the generated content, then press q to continue"),
+        };
+        screen.refresh();
+        screen
+    }
+
+    /// Runs the screen until the user is done, returning the reviewed content.
+    pub async fn run(mut self) -> Result<LLMResumeOutput> {
+        let mut terminal = ratatui::init();
+        let result = self.event_loop(&mut terminal).await;
+        ratatui::restore();
+
+        result.map(|()| self.output)
+    }
+
+    async fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                return Err(eyre!("review aborted"));
+            }
+
+            if self.editing.is_some() {
+                if let Some((target, feedback)) = self.handle_input_key(key) {
+                    self.regenerate(terminal, target, Some(&feedback)).await?;
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select_by(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select_by(1),
+                KeyCode::Char('K') => self.move_selected(-1),
+                KeyCode::Char('J') => self.move_selected(1),
+                KeyCode::Enter | KeyCode::Char('e') => self.start_edit(),
+                KeyCode::Char('d') | KeyCode::Delete => self.delete_selected(),
+                KeyCode::Char('r') => self.regenerate(terminal, self.target(), None).await?,
+                KeyCode::Char('f') => self.start_feedback(),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                _ => {}
+            }
+        }
+    }
+
+    /// Rebuilds the row list and the preview after the content changed.
+    fn refresh(&mut self) {
+        self.rows = OutputSection::ALL
+            .into_iter()
+            .flat_map(|section| {
+                let output = &self.output;
+                std::iter::once(Row::Section(section)).chain((0..section.count(output)).flat_map(
+                    move |entry| {
+                        std::iter::once(Row::Entry(section, entry)).chain(
+                            (0..section.bullets(output, entry).len())
+                                .map(move |bullet| Row::Bullet(section, entry, bullet)),
+                        )
+                    },
+                ))
+            })
+            .collect();
+
+        let selected = self.list.selected().unwrap_or(0);
+        self.list.select(Some(selected.min(self.rows.len() - 1)));
+
+        self.preview = TextResumeRenderer::new(
+            apply_resume_output(self.config, &self.output),
+            self.input.language.clone(),
+        )
+        .render()
+        .unwrap_or_else(|e| format!("failed to render the preview: {}", e));
+    }
+
+    fn selected(&self) -> Row {
+        self.rows[self.list.selected().unwrap_or(0)]
+    }
+
+    /// The selected section, or the entry the selected row belongs to.
+    fn target(&self) -> OutputTarget {
+        match self.selected() {
+            Row::Section(section) => OutputTarget::Section(section),
+            Row::Entry(section, entry) | Row::Bullet(section, entry, _) => {
+                OutputTarget::Entry(section, entry)
+            }
+        }
+    }
+
+    fn select_row(&mut self, row: Row) {
+        if let Some(index) = self.rows.iter().position(|r| *r == row) {
+            self.list.select(Some(index));
+        }
+    }
+
+    fn select_by(&mut self, delta: isize) {
+        let selected = self.list.selected().unwrap_or(0);
+        let index = selected
+            .saturating_add_signed(delta)
+            .min(self.rows.len() - 1);
+        self.list.select(Some(index));
+    }
+
+    fn move_selected(&mut self, delta: isize) {
+        match self.selected() {
+            Row::Section(_) => {
+                self.status = String::from("sections are ordered by the template");
+            }
+            Row::Entry(section, entry) => {
+                let Some(target) = entry
+                    .checked_add_signed(delta)
+                    .filter(|&target| target < section.count(&self.output))
+                else {
+                    return;
+                };
+                section.swap(&mut self.output, entry, target);
+                self.refresh();
+                self.select_row(Row::Entry(section, target));
+            }
+            Row::Bullet(section, entry, bullet) => {
+                let bullets = section.bullets_mut(&mut self.output, entry);
+                let Some(target) = bullet
+                    .checked_add_signed(delta)
+                    .filter(|&target| target < bullets.len())
+                else {
+                    return;
+                };
+                bullets.swap(bullet, target);
+                self.refresh();
+                self.select_row(Row::Bullet(section, entry, target));
+            }
+        }
+    }
+
+    fn delete_selected(&mut self) {
+        match self.selected() {
+            Row::Section(_) => {
+                self.status = String::from("select an entry or a bullet point to delete");
+                return;
+            }
+            Row::Entry(section, entry) => {
+                self.status = format!("deleted \"{}\"", section.title(&self.output, entry));
+                section.remove(&mut self.output, entry);
+            }
+            Row::Bullet(section, entry, bullet) => {
+                section.bullets_mut(&mut self.output, entry).remove(bullet);
+                self.status = String::from("deleted bullet point");
+            }
+        }
+        self.refresh();
+    }
+
+    fn start_edit(&mut self) {
+        let row = self.selected();
+        let text = match row {
+            Row::Section(_) => {
+                self.status = String::from("select an entry or a bullet point to edit");
+                return;
+            }
+            Row::Entry(section, entry) => section.title(&self.output, entry),
+            Row::Bullet(section, entry, bullet) => &section.bullets(&self.output, entry)[bullet],
+        };
+
+        let text: Vec<char> = text.chars().collect();
+        self.editing = Some(Input {
+            prompt: Prompt::Edit(row),
+            cursor: text.len(),
+            text,
+        });
+    }
+
+    fn start_feedback(&mut self) {
+        self.editing = Some(Input {
+            prompt: Prompt::Feedback(self.target()),
+            text: Vec::new(),
+            cursor: 0,
+        });
+    }
+
+    /// Handles a key while the text input is open. Returns the target and
+    /// feedback to regenerate with once feedback is submitted.
+    fn handle_input_key(&mut self, key: KeyEvent) -> Option<(OutputTarget, String)> {
+        let input = self.editing.as_mut()?;
+
+        match key.code {
+            KeyCode::Esc => self.editing = None,
+            KeyCode::Enter => {
+                let text: String = input.text.iter().collect::<String>().trim().to_string();
+                if text.is_empty() {
+                    self.status = match input.prompt {
+                        Prompt::Edit(_) => "text can't be empty, press d to delete instead",
+                        Prompt::Feedback(_) => {
+                            "enter some feedback, or press r to regenerate without it"
+                        }
+                    }
+                    .to_string();
+                    return None;
+                }
+
+                let prompt = input.prompt;
+                self.editing = None;
+                match prompt {
+                    Prompt::Edit(Row::Section(_)) => {}
+                    Prompt::Edit(Row::Entry(section, entry)) => {
+                        *section.title_mut(&mut self.output, entry) = text;
+                    }
+                    Prompt::Edit(Row::Bullet(section, entry, bullet)) => {
+                        section.bullets_mut(&mut self.output, entry)[bullet] = text;
+                    }
+                    Prompt::Feedback(target) => return Some((target, text)),
+                }
+                self.status = String::from("saved");
+                self.refresh();
+            }
+            KeyCode::Backspace if input.cursor > 0 => {
+                input.cursor -= 1;
+                input.text.remove(input.cursor);
+            }
+            KeyCode::Delete if input.cursor < input.text.len() => {
+                input.text.remove(input.cursor);
+            }
+            KeyCode::Left => input.cursor = input.cursor.saturating_sub(1),
+            KeyCode::Right => input.cursor = (input.cursor + 1).min(input.text.len()),
+            KeyCode::Home => input.cursor = 0,
+            KeyCode::End => input.cursor = input.text.len(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                input.text.insert(input.cursor, c);
+                input.cursor += 1;
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    async fn regenerate(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        target: OutputTarget,
+        feedback: Option<&str>,
+    ) -> Result<()> {
+        self.status = format!("regenerating {}...", target.describe(&self.output));
+        terminal.draw(|frame| self.draw(frame))?;
+
+        match self
+            .agent
+            .regenerate(&self.input, &self.output, target, feedback)
+            .await
+        {
+            Ok(output) => {
+                self.output = output;
+                self.status = format!("regenerated {}", target.describe(&self.output));
+                self.refresh();
+                self.select_row(match target {
+                    OutputTarget::Section(section) => Row::Section(section),
+                    OutputTarget::Entry(section, entry) => Row::Entry(section, entry),
+                });
+            }
+            Err(e) => self.status = format!("regeneration failed: {}", e),
+        }
+
+        // provider logging may have written over the screen
+        terminal.clear()?;
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+        let [content, preview] =
+            Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .areas(main);
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| ListItem::new(row_line(&self.output, *row)))
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Resume content "))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, content, &mut self.list);
+
+        frame.render_widget(
+            Paragraph::new(self.preview.as_str())
+                .block(Block::bordered().title(" Preview "))
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            preview,
+        );
+
+        match &self.editing {
+            Some(input) => self.draw_input(frame, footer, input),
+            None => frame.render_widget(
+                Paragraph::new(self.status.as_str()).block(Block::bordered().title(HELP)),
+                footer,
+            ),
+        }
+    }
+
+    fn draw_input(&self, frame: &mut Frame, area: Rect, input: &Input) {
+        // scroll horizontally so the cursor stays visible
+        let width = area.width.saturating_sub(2) as usize;
+        let offset = (input.cursor + 1).saturating_sub(width);
+        let visible: String = input.text.iter().skip(offset).take(width).collect();
+
+        let title = match input.prompt {
+            Prompt::Edit(_) => String::from(" Edit · Enter save · Esc cancel "),
+            Prompt::Feedback(target) => format!(
+                " Feedback for {} · Enter regenerate · Esc cancel ",
+                target.describe(&self.output)
+            ),
+        };
+
+        frame.render_widget(
+            Paragraph::new(visible).block(Block::bordered().title(title)),
+            area,
+        );
+        frame.set_cursor_position((area.x + 1 + (input.cursor - offset) as u16, area.y + 1));
+    }
+}
+
+fn row_line(output: &LLMResumeOutput, row: Row) -> Line<'static> {
+    match row {
+        Row::Section(section) => {
+            Line::from(format!("{} ({})", section.label(), section.count(output))).bold()
+        }
+        Row::Entry(section, entry) => Line::from(format!("  {}", section.title(output, entry))),
+        Row::Bullet(section, entry, bullet) => {
+            Line::from(format!("    • {}", section.bullets(output, entry)[bullet]))
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use eyre::Result;
 use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 const CACHE_DIR: &str = ".readme-cache";
+const RESPONSE_CACHE_DIR: &str = ".github-cache";
 
 /// Initializes the cache directory if it doesn't exist
 pub fn init_cache() -> Result<PathBuf> {
@@ -50,3 +53,54 @@ pub fn cache_readme(repo_name: &str, content: &str) -> Result<()> {
     debug!("cached README for: {}", repo_name);
     Ok(())
 }
+
+/// An API response, kept to answer `304 Not Modified` replies
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: String,
+    /// Pagination `link` header
+    pub link: Option<String>,
+    pub body: String,
+}
+
+/// Gets the cache file path for a request, keyed by URL and token (responses
+/// differ with the token's access). SHA-256, unlike the std hasher, stays the
+/// same across Rust releases
+fn get_response_cache_file(url: &str, token: Option<&str>) -> PathBuf {
+    let mut hasher = Sha256::new();
+    hasher.update(url);
+    // separates the URL from the token, and no token from an empty one
+    match token {
+        Some(token) => {
+            hasher.update([1]);
+            hasher.update(token);
+        }
+        None => hasher.update([0]),
+    }
+    let digest = hasher.finalize();
+    let name: String = digest[..8].iter().map(|byte| format!("{:02x}", byte)).collect();
+    Path::new(RESPONSE_CACHE_DIR).join(format!("{}.json", name))
+}
+
+/// Retrieves a stored API response if it exists
+pub fn get_cached_response(url: &str, token: Option<&str>) -> Option<CachedResponse> {
+    let content = fs::read_to_string(get_response_cache_file(url, token)).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(response) => Some(response),
+        Err(e) => {
+            debug!("failed to read cached response for {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// Stores an API response with its ETag
+pub fn cache_response(url: &str, token: Option<&str>, response: &CachedResponse) -> Result<()> {
+    fs::create_dir_all(RESPONSE_CACHE_DIR)?;
+    fs::write(
+        get_response_cache_file(url, token),
+        serde_json::to_string(response)?,
+    )?;
+    debug!("cached response for: {}", url);
+    Ok(())
+}
@@ -1,16 +1,30 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use log::LevelFilter;
 
+use crate::analysis::guard::GuardMode;
+use crate::chat::section::OutputSection;
 use crate::latex::assembler::ResumeLanguage;
+use crate::latex::template::TemplateStyle;
+use crate::pipeline::artifacts::Stage;
+use crate::render::renderer::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(name = "auto-resume")]
 #[command(about = "Generate resumes tailored to job postings using GitHub data and AI", long_about = None)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the configuration file
-    #[arg(short, long, value_name = "FILE", default_value = "config.toml")]
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        default_value = "config.toml",
+        global = true
+    )]
     pub config: PathBuf,
 
     /// URL to the job posting (LinkedIn, Indeed, etc.)
@@ -21,19 +35,134 @@ pub struct Args {
     #[arg(long, value_name = "FILE")]
     pub job_file: Option<PathBuf>,
 
-    /// Resume language: en (English) or pt (Portuguese)
-    #[arg(short, long, value_name = "LANG", default_value = "pt")]
-    pub language: ResumeLanguage,
+    /// Resume language: en (English) or pt (Portuguese) [default: pt, or the resumed run's language]
+    #[arg(short, long, value_name = "LANG", global = true)]
+    pub language: Option<ResumeLanguage>,
 
-    /// Output PDF file path
+    /// Output file path (the extension is replaced for non-PDF formats)
     #[arg(short, long, value_name = "FILE", default_value = "resume.pdf")]
     pub output: PathBuf,
 
+    /// Output format: latex (compiled to PDF), markdown, html, text or docx
+    #[arg(short, long, value_name = "FORMAT", value_enum, default_value_t = OutputFormat::Latex, global = true)]
+    pub format: OutputFormat,
+
+    /// Bundled LaTeX layout: classic, compact, modern or academic, overriding the `template` config key
+    #[arg(long, value_name = "STYLE", value_enum, global = true)]
+    pub style: Option<TemplateStyle>,
+
+    /// Fit the PDF resume in at most N pages, overriding the `max_pages` config key
+    #[arg(long, value_name = "N", global = true)]
+    pub max_pages: Option<usize>,
+
+    /// Custom LaTeX template, overriding the `template` config key and `--style`
+    #[arg(long, value_name = "FILE", global = true)]
+    pub template: Option<PathBuf>,
+
     /// Enable saving intermediate LaTeX file
-    #[arg(long, action = clap::ArgAction::SetTrue)]
+    #[arg(long, action = clap::ArgAction::SetTrue, global = true)]
     pub latex: bool,
 
+    /// Also export the generated resume content as JSON Resume (jsonresume.org)
+    #[arg(long, value_name = "FILE")]
+    pub export_json_resume: Option<PathBuf>,
+
+    /// Read the selected repositories' code for a tech fingerprint, overriding the `deep` config key
+    #[arg(long, action = clap::ArgAction::SetTrue, global = true)]
+    pub deep_analysis: bool,
+
+    /// Abort before an LLM call would take the estimated cost past USD, overriding the `max_cost` config key
+    #[arg(long, value_name = "USD", global = true)]
+    pub max_cost: Option<f64>,
+
+    /// Unverifiable generated claims: off, flag (log them) or strip (remove them)
+    #[arg(long, value_name = "MODE", value_enum, global = true)]
+    pub guard: Option<GuardMode>,
+
+    /// Also generate a cover letter for the job, as <OUTPUT>-cover-letter.pdf
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub cover_letter: bool,
+
+    /// Also save the ATS keyword coverage report as JSON
+    #[arg(long, value_name = "FILE")]
+    pub ats_report: Option<PathBuf>,
+
+    /// Never prompt on stdin: select repositories automatically and skip the editor
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub non_interactive: bool,
+
+    /// Skip the review screen shown after the content is generated
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub no_review: bool,
+
+    /// Number of top-ranked repositories to use in non-interactive mode
+    #[arg(long, value_name = "N", default_value_t = 5, global = true)]
+    pub top: usize,
+
+    /// Explicit repositories to include, by name (implies non-interactive selection)
+    #[arg(
+        long,
+        value_name = "REPOS",
+        value_delimiter = ',',
+        conflicts_with = "selection_file"
+    )]
+    pub repos: Vec<String>,
+
+    /// File listing repositories to include, one name per line (implies non-interactive selection)
+    #[arg(long, value_name = "FILE")]
+    pub selection_file: Option<PathBuf>,
+
+    /// Directory to save the run's stage artifacts in (default: runs/<timestamp>)
+    #[arg(long, value_name = "DIR", conflicts_with = "resume_from")]
+    pub run_dir: Option<PathBuf>,
+
+    /// Resume a previous run from its artifact directory
+    #[arg(long, value_name = "DIR")]
+    pub resume_from: Option<PathBuf>,
+
+    /// Stage to restart from when resuming (default: first stage without an artifact)
+    #[arg(long, value_name = "STAGE", requires = "resume_from")]
+    pub from_stage: Option<Stage>,
+
     /// Sets the logger's verbosity level
-    #[arg(short, long, value_name = "VERBOSITY", default_value_t = LevelFilter::Info)]
+    #[arg(short, long, value_name = "VERBOSITY", default_value_t = LevelFilter::Info, global = true)]
     pub verbosity: LevelFilter,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Generate resumes for every job posting listed in a manifest file
+    Batch(BatchArgs),
+    /// Regenerate one section or entry of an earlier run's content, keeping the rest
+    Regenerate(RegenerateArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct BatchArgs {
+    /// Manifest of jobs: TOML with `[[job]]` tables, or JSON Lines (.jsonl)
+    #[arg(value_name = "MANIFEST")]
+    pub manifest: PathBuf,
+
+    /// Maximum number of jobs processed at the same time
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub concurrency: usize,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RegenerateArgs {
+    /// Run directory holding the generated content (see --run-dir)
+    #[arg(value_name = "RUN_DIR")]
+    pub run: PathBuf,
+
+    /// Section to regenerate
+    #[arg(long, value_name = "SECTION", value_enum)]
+    pub section: OutputSection,
+
+    /// Regenerate only this entry, by position (1-based) or title, e.g. a project name
+    #[arg(long, value_name = "ENTRY")]
+    pub entry: Option<String>,
+
+    /// What to change, e.g. "more emphasis on distributed systems"
+    #[arg(long, value_name = "TEXT")]
+    pub feedback: Option<String>,
+}
@@ -1,16 +1,48 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use easy_config_store::ConfigStore;
-use eyre::Result;
+use eyre::{Result, eyre};
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
 
+use crate::analysis::guard::GuardMode;
+use crate::chat::provider::ProviderKind;
+use crate::latex::template::TemplateStyle;
+use crate::models::json_resume::JsonResume;
+use crate::scraper::forge::ForgeKind;
+use crate::scraper::github::{Affiliation, PrivateLinks};
+
 pub type Config = Arc<ConfigInner>;
 
 pub fn config(path: &PathBuf) -> Result<Config> {
     let config_store = ConfigStore::<ConfigInner>::read(path, "config".to_string())?;
-    let inner = (*config_store).clone();
+    let mut inner = (*config_store).clone();
+
+    if let Some(json_resume) = inner.resume.json_resume.clone() {
+        let base = path.parent().unwrap_or(Path::new("."));
+        import_json_resume(&mut inner.resume, &base.join(json_resume))?;
+    }
+
+    if let Some(template) = &inner.latex.template {
+        let base = path.parent().unwrap_or(Path::new("."));
+        inner.latex.template = Some(base.join(template));
+    }
+
+    for local in &mut inner.local_repos {
+        let base = path.parent().unwrap_or(Path::new("."));
+        local.path = base.join(&local.path);
+    }
+
+    if inner.resume.full_name.trim().is_empty() {
+        return Err(eyre!(
+            "resume full_name is missing (set it in the config or in the JSON Resume basics)"
+        ));
+    }
+
+    // fail before scraping rather than at the first LLM call
+    inner.llm.model()?;
 
     info!("config parsing successful");
     debug!("loaded configuration:\n{}", toml::to_string_pretty(&inner)?);
@@ -18,17 +50,44 @@ pub fn config(path: &PathBuf) -> Result<Config> {
     Ok(Arc::new(inner))
 }
 
+fn import_json_resume(resume: &mut ResumeConfig, path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("failed to read JSON Resume {}: {}", path.display(), e))?;
+    let json_resume: JsonResume = serde_json::from_str(&content)
+        .map_err(|e| eyre!("invalid JSON Resume {}: {}", path.display(), e))?;
+
+    json_resume.apply_to(resume);
+    info!("imported resume data from {}", path.display());
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ConfigInner {
     pub resume: ResumeConfig,
     pub github: GithubConfig,
+    /// GitLab and Gitea/Forgejo accounts scraped alongside GitHub
+    #[serde(default)]
+    pub forges: Vec<ForgeConfig>,
+    /// Clones on this machine, analyzed from their Git history
+    #[serde(default)]
+    pub local_repos: Vec<LocalRepoConfig>,
     pub llm: LLMConfig,
+    #[serde(default)]
+    pub latex: LatexConfig,
+    #[serde(default)]
+    pub analysis: AnalysisConfig,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ResumeConfig {
+    /// JSON Resume file (jsonresume.org) filling in whatever this config leaves out
+    pub json_resume: Option<PathBuf>,
+    #[serde(default)]
     pub full_name: String,
+    #[serde(default)]
     pub country: String,
+    #[serde(default)]
     pub city: String,
     pub email: Option<String>,
     pub phone: Option<String>,
@@ -43,6 +102,12 @@ pub struct ResumeConfig {
     pub experience: Vec<ResumeItem>,
     #[serde(default)]
     pub projects: Vec<ResumeItem>,
+    /// Open source contributions to other people's projects
+    #[serde(default)]
+    pub contributions: Vec<ResumeItem>,
+    /// Papers and talks, shown by the academic style
+    #[serde(default)]
+    pub publications: Vec<ResumeItem>,
     pub education_context: Option<String>,
     pub experience_context: Option<String>,
     pub skills_context: Option<String>,
@@ -61,32 +126,214 @@ pub struct ResumeItem {
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GithubConfig {
     pub token: Option<String>,
+    /// Left empty to skip GitHub, e.g. for offline runs over local repositories
+    #[serde(default)]
     pub username: String,
+    /// REST API of a GitHub Enterprise Server, like
+    /// `https://github.example.com/api/v3` (default: api.github.com)
+    pub api_url: Option<String>,
+    /// With a token, list `/user/repos` with these affiliations (private and
+    /// collaborator repositories included) instead of public, user-owned ones
+    #[serde(default)]
+    pub affiliation: Vec<Affiliation>,
+    /// Organizations whose repositories are scraped as well
+    #[serde(default)]
+    pub orgs: Vec<String>,
+    #[serde(default)]
+    pub private_links: PrivateLinks,
+    /// Search merged pull requests to other people's repositories
+    #[serde(default = "default_contributions")]
+    pub contributions: bool,
+    /// With a token, list repositories through the GraphQL API, a few
+    /// requests in total instead of several per repository
+    #[serde(default = "default_graphql")]
+    pub graphql: bool,
+}
+
+impl GithubConfig {
+    pub fn api_url(&self) -> &str {
+        self.api_url
+            .as_deref()
+            .unwrap_or("https://api.github.com")
+            .trim_end_matches('/')
+    }
+
+    /// Where the projects are hosted, as shown on the resume.
+    pub fn label(&self) -> &'static str {
+        match self.api_url() {
+            "https://api.github.com" => "GitHub",
+            _ => "GitHub Enterprise",
+        }
+    }
+
+    /// GitHub Enterprise Server serves GraphQL at `/api/graphql`, next to the
+    /// REST API's `/api/v3`.
+    pub fn graphql_url(&self) -> String {
+        let api_url = self.api_url();
+        match api_url.strip_suffix("/api/v3") {
+            Some(base) => format!("{}/api/graphql", base),
+            None => format!("{}/graphql", api_url),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ForgeConfig {
+    pub kind: ForgeKind,
+    /// Instance URL (default: gitlab.com for GitLab, codeberg.org for Gitea)
+    pub url: Option<String>,
+    pub username: String,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct LocalRepoConfig {
+    pub path: PathBuf,
+    /// Project name (default: the directory name)
+    pub name: Option<String>,
+    /// Public page of the repository, if any; without one the repository is
+    /// treated as private
+    pub url: Option<String>,
+    /// Commit emails counted as yours (default: the repository's
+    /// `user.email` and the resume email)
+    #[serde(default)]
+    pub emails: Vec<String>,
+}
+
+impl ForgeConfig {
+    pub fn url(&self) -> &str {
+        self.url
+            .as_deref()
+            .unwrap_or(self.kind.default_url())
+            .trim_end_matches('/')
+    }
+
+    /// Where the projects are hosted, as shown on the resume.
+    pub fn label(&self) -> &'static str {
+        match (self.kind, self.host()) {
+            (ForgeKind::GitLab, _) => "GitLab",
+            (ForgeKind::Gitea, "codeberg.org") => "Codeberg",
+            (ForgeKind::Gitea, _) => "Gitea",
+        }
+    }
+
+    /// The instance's host, for log messages.
+    pub fn host(&self) -> &str {
+        self.url()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct LLMConfig {
+    #[serde(default)]
+    pub provider: ProviderKind,
     pub api_key: Option<String>,
-    #[serde(default = "default_llm_model")]
-    pub model: String,
-    #[serde(default = "default_llm_endpoint")]
-    pub endpoint: String,
+    /// Required for every provider but Gemini
+    pub model: Option<String>,
+    pub endpoint: Option<String>,
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
+    /// Handling of generated claims that can't be traced to source data
+    #[serde(default)]
+    pub guard: GuardMode,
+    /// How much of the repositories' READMEs goes into the prompt
+    #[serde(default)]
+    pub readme: ReadmeConfig,
+    /// Prices by model name, for the cost estimate
+    #[serde(default)]
+    pub prices: HashMap<String, ModelPrice>,
+    /// Estimated cost in USD no run may exceed
+    pub max_cost: Option<f64>,
 }
 
-fn default_llm_model() -> String {
-    "gemini-3-flash-preview".to_string()
+impl LLMConfig {
+    /// The configured model, or the provider's default one.
+    pub fn model(&self) -> Result<&str> {
+        match (&self.model, self.provider.default_model()) {
+            (Some(model), _) => Ok(model),
+            (None, Some(model)) => Ok(model),
+            (None, None) => Err(eyre!(
+                "set `model` under [config.llm] for the {} provider",
+                self.provider.name()
+            )),
+        }
+    }
 }
 
-fn default_llm_endpoint() -> String {
-    "https://generativelanguage.googleapis.com/v1beta/models".to_string()
+/// USD per million tokens
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ReadmeConfig {
+    /// Tokens per README, after badges, images, HTML and long code blocks are stripped
+    #[serde(default = "default_readme_max_tokens")]
+    pub max_tokens: usize,
+    /// Tokens for all READMEs in a prompt together
+    #[serde(default = "default_readme_max_prompt_tokens")]
+    pub max_prompt_tokens: usize,
+    /// Code blocks longer than this are left out
+    #[serde(default = "default_readme_max_code_lines")]
+    pub max_code_lines: usize,
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        ReadmeConfig {
+            max_tokens: default_readme_max_tokens(),
+            max_prompt_tokens: default_readme_max_prompt_tokens(),
+            max_code_lines: default_readme_max_code_lines(),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct LatexConfig {
+    #[serde(default)]
+    pub style: TemplateStyle,
+    /// Page limit enforced by tightening the layout and shortening content
+    pub max_pages: Option<usize>,
+    /// Custom LaTeX template (MiniJinja, `\VAR{}`/`\BLOCK{}` delimiters) used instead of the stock one
+    pub template: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct AnalysisConfig {
+    /// Read the selected repositories' code (shallow clones, or local
+    /// repositories in place) for their dependencies, CI, containers and tests
+    #[serde(default)]
+    pub deep: bool,
 }
 
 fn default_max_retries() -> u32 {
     3
 }
 
+fn default_readme_max_tokens() -> usize {
+    1500
+}
+
+fn default_readme_max_prompt_tokens() -> usize {
+    20000
+}
+
+fn default_readme_max_code_lines() -> usize {
+    15
+}
+
+fn default_contributions() -> bool {
+    true
+}
+
+fn default_graphql() -> bool {
+    true
+}
+
 impl Default for ConfigInner {
     fn default() -> Self {
         let cfg = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/config.default.toml",));
@@ -1,15 +1,161 @@
 use std::io::{self, Write};
 
 use colored::Colorize;
-use log::info;
+use eyre::{Result, eyre};
+use log::{info, warn};
 
+use crate::analysis::guard::repo_key;
 use crate::chat::agent::RankedRepository;
-use crate::scraper::github::GitHubRepoData;
+use crate::scraper::forge::RepoData;
+use crate::utils::cli::Args;
+
+/// How repositories are picked when no one is around to answer prompts.
+#[derive(Debug, Clone)]
+pub enum RepoSelection {
+    /// The first `n` repositories in ranking order
+    Top(usize),
+    /// An explicit list of repository names
+    Names(Vec<String>),
+}
+
+impl RepoSelection {
+    /// Builds the selection requested on the command line, or `None` when the
+    /// interactive prompt should be used.
+    pub fn from_args(args: &Args) -> Result<Option<Self>> {
+        if !args.repos.is_empty() {
+            return Ok(Some(RepoSelection::Names(
+                args.repos.iter().map(|r| r.trim().to_string()).collect(),
+            )));
+        }
+
+        if let Some(path) = &args.selection_file {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| eyre!("failed to read selection file {}: {}", path.display(), e))?;
+            return Ok(Some(RepoSelection::Names(parse_selection_file(&content))));
+        }
+
+        if args.non_interactive {
+            return Ok(Some(RepoSelection::Top(args.top)));
+        }
+
+        Ok(None)
+    }
+}
+
+/// Parses a selection file: one repository per line, commas also accepted,
+/// blank lines and `#` comments ignored.
+fn parse_selection_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The repository `name` refers to, by full name (`owner/name`) or else by
+/// bare name, the first one when several owners have a repository by that
+/// name.
+fn find_repo<'a>(repos: &'a [RepoData], name: &str) -> Option<&'a RepoData> {
+    let key = repo_key(name);
+    let suffix = format!("/{}", key);
+    repos
+        .iter()
+        .find(|repo| repo.full_name() == key)
+        .or_else(|| {
+            let mut matches = repos
+                .iter()
+                .filter(|repo| repo.full_name().ends_with(&suffix));
+            let repo = matches.next()?;
+            if matches.next().is_some() {
+                warn!(
+                    "several repositories are named {}, using {} (give owner/name to pick another)",
+                    name,
+                    repo.full_name()
+                );
+            }
+            Some(repo)
+        })
+}
+
+/// The `n` best-ranked repositories, skipping ranked names that match no
+/// scraped repository.
+fn top_ranked(ranked: &[RankedRepository], all_repos: &[RepoData], n: usize) -> Vec<RepoData> {
+    let mut ranked: Vec<&RankedRepository> = ranked.iter().collect();
+    ranked.sort_by_key(|r| r.rank);
+
+    let mut selected: Vec<RepoData> = Vec::new();
+    for r in ranked {
+        if selected.len() == n {
+            break;
+        }
+        match find_repo(all_repos, &r.name) {
+            Some(repo) if !selected.iter().any(|s| s.url == repo.url) => {
+                selected.push(repo.clone())
+            }
+            Some(_) => {}
+            None => warn!("ranked repository not found in profile: {}", r.name),
+        }
+    }
+
+    if selected.len() < n {
+        warn!(
+            "only {} of the {} requested repositories could be selected from the ranking",
+            selected.len(),
+            n
+        );
+    }
+    selected
+}
+
+pub fn select_repositories_non_interactive(
+    ranked: Vec<RankedRepository>,
+    all_repos: &[RepoData],
+    selection: &RepoSelection,
+) -> Result<Vec<RepoData>> {
+    let selected_repos: Vec<RepoData> = match selection {
+        RepoSelection::Top(n) => top_ranked(&ranked, all_repos, *n),
+        RepoSelection::Names(names) => {
+            let mut selected_repos: Vec<RepoData> = Vec::new();
+            for name in names {
+                // accept both 'repo-name' and 'owner/repo-name'
+                match find_repo(all_repos, name) {
+                    Some(repo) if !selected_repos.iter().any(|r| r.url == repo.url) => {
+                        selected_repos.push(repo.clone())
+                    }
+                    Some(_) => warn!("repository listed twice in selection: {}", name),
+                    None => warn!("selected repository not found in profile: {}", name),
+                }
+            }
+            selected_repos
+        }
+    };
+
+    if selected_repos.is_empty() {
+        return Err(eyre!(
+            "no repositories matched the non-interactive selection"
+        ));
+    }
+
+    info!(
+        "non-interactive selection: {} repositories for resume ({})",
+        selected_repos.len(),
+        selected_repos
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    Ok(selected_repos)
+}
 
 pub fn select_repositories_interactive(
     ranked: Vec<RankedRepository>,
-    all_repos: &[GitHubRepoData],
-) -> Vec<GitHubRepoData> {
+    all_repos: &[RepoData],
+) -> Vec<RepoData> {
     println!("\n{}", "=== Repository Selection ===".cyan().bold());
     println!(
         "{}\n",
@@ -41,11 +187,7 @@ pub fn select_repositories_interactive(
                 "{}",
                 "No repositories selected. Using top 5 by default.".yellow()
             );
-            return ranked
-                .iter()
-                .take(5)
-                .filter_map(|r| all_repos.iter().find(|repo| repo.name == r.name).cloned())
-                .collect();
+            return top_ranked(&ranked, all_repos, 5);
         }
 
         let mut selected_repos = Vec::new();
@@ -55,9 +197,7 @@ pub fn select_repositories_interactive(
             match num_str.trim().parse::<usize>() {
                 Ok(num) => {
                     if let Some(ranked_repo) = ranked.iter().find(|r| r.rank == num) {
-                        if let Some(repo) =
-                            all_repos.iter().find(|repo| repo.name == ranked_repo.name)
-                        {
+                        if let Some(repo) = find_repo(all_repos, &ranked_repo.name) {
                             selected_repos.push(repo.clone());
                         }
                     } else {
@@ -118,11 +258,8 @@ pub fn select_repositories_interactive(
 
                             for repo_name in manual_input.split(',') {
                                 let repo_name = repo_name.trim();
-                                if let Some(existing) =
-                                    all_repos.iter().find(|repo| repo.name == repo_name)
-                                {
-                                    if !selected_repos.iter().any(|repo| repo.name == existing.name)
-                                    {
+                                if let Some(existing) = find_repo(all_repos, repo_name) {
+                                    if !selected_repos.iter().any(|repo| repo.url == existing.url) {
                                         selected_repos.push(existing.clone());
                                         println!("{}", format!("✓ Added: {}", repo_name).green());
                                     } else {